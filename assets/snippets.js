@@ -10,3 +10,157 @@ export function is_mobile() {
   (function(a) { if (/(android|bb\d+|meego).+mobile|avantgo|bada\/|blackberry|blazer|compal|elaine|fennec|hiptop|iemobile|ip(hone|od)|iris|kindle|lge |maemo|midp|mmp|mobile.+firefox|netfront|opera m(ob|in)i|palm( os)?|phone|p(ixi|re)\/|plucker|pocket|psp|series(4|6)0|symbian|treo|up\.(browser|link)|vodafone|wap|windows ce|xda|xiino/i.test(a) || /1207|6310|6590|3gso|4thp|50[1-6]i|770s|802s|a wa|abac|ac(er|oo|s\-)|ai(ko|rn)|al(av|ca|co)|amoi|an(ex|ny|yw)|aptu|ar(ch|go)|as(te|us)|attw|au(di|\-m|r |s )|avan|be(ck|ll|nq)|bi(lb|rd)|bl(ac|az)|br(e|v)w|bumb|bw\-(n|u)|c55\/|capi|ccwa|cdm\-|cell|chtm|cldc|cmd\-|co(mp|nd)|craw|da(it|ll|ng)|dbte|dc\-s|devi|dica|dmob|do(c|p)o|ds(12|\-d)|el(49|ai)|em(l2|ul)|er(ic|k0)|esl8|ez([4-7]0|os|wa|ze)|fetc|fly(\-|_)|g1 u|g560|gene|gf\-5|g\-mo|go(\.w|od)|gr(ad|un)|haie|hcit|hd\-(m|p|t)|hei\-|hi(pt|ta)|hp( i|ip)|hs\-c|ht(c(\-| |_|a|g|p|s|t)|tp)|hu(aw|tc)|i\-(20|go|ma)|i230|iac( |\-|\/)|ibro|idea|ig01|ikom|im1k|inno|ipaq|iris|ja(t|v)a|jbro|jemu|jigs|kddi|keji|kgt( |\/)|klon|kpt |kwc\-|kyo(c|k)|le(no|xi)|lg( g|\/(k|l|u)|50|54|\-[a-w])|libw|lynx|m1\-w|m3ga|m50\/|ma(te|ui|xo)|mc(01|21|ca)|m\-cr|me(rc|ri)|mi(o8|oa|ts)|mmef|mo(01|02|bi|de|do|t(\-| |o|v)|zz)|mt(50|p1|v )|mwbp|mywa|n10[0-2]|n20[2-3]|n30(0|2)|n50(0|2|5)|n7(0(0|1)|10)|ne((c|m)\-|on|tf|wf|wg|wt)|nok(6|i)|nzph|o2im|op(ti|wv)|oran|owg1|p800|pan(a|d|t)|pdxg|pg(13|\-([1-8]|c))|phil|pire|pl(ay|uc)|pn\-2|po(ck|rt|se)|prox|psio|pt\-g|qa\-a|qc(07|12|21|32|60|\-[2-7]|i\-)|qtek|r380|r600|raks|rim9|ro(ve|zo)|s55\/|sa(ge|ma|mm|ms|ny|va)|sc(01|h\-|oo|p\-)|sdk\/|se(c(\-|0|1)|47|mc|nd|ri)|sgh\-|shar|sie(\-|m)|sk\-0|sl(45|id)|sm(al|ar|b3|it|t5)|so(ft|ny)|sp(01|h\-|v\-|v )|sy(01|mb)|t2(18|50)|t6(00|10|18)|ta(gt|lk)|tcl\-|tdg\-|tel(i|m)|tim\-|t\-mo|to(pl|sh)|ts(70|m\-|m3|m5)|tx\-9|up(\.b|g1|si)|utst|v400|v750|veri|vi(rg|te)|vk(40|5[0-3]|\-v)|vm40|voda|vulc|vx(52|53|60|61|70|80|81|83|85|98)|w3c(\-| )|webc|whit|wi(g |nc|nw)|wmlb|wonu|x700|yas\-|your|zeto|zte\-/i.test(a.substr(0, 4))) is_mobile = true; })(navigator.userAgent || navigator.vendor || window.opera);
   return is_mobile;
 }
+
+/** Reloads the page */
+export function reload() {
+  window.location.reload();
+}
+
+/** Returns the value of the given query parameter in the page url, or null if it is absent */
+export function query_param(name) {
+  return new URLSearchParams(window.location.search).get(name);
+}
+
+/** Returns the current time, in milliseconds since the unix epoch */
+export function now() {
+  return Date.now();
+}
+
+/** Formats a time, in milliseconds since the unix epoch, as a local "HH:MM:SS" string */
+export function format_time(time) {
+  return new Date(time).toTimeString().slice(0, 8);
+}
+
+/** Returns the fragment of the page url, without the leading "#" */
+export function get_hash() {
+  return window.location.hash.replace(/^#/, "");
+}
+
+/** Sets the fragment of the page url, without adding a history entry */
+export function set_hash(hash) {
+  history.replaceState(null, "", "#" + hash);
+}
+
+/** Returns the width of the browser window, in css pixels */
+export function inner_width() {
+  return window.innerWidth;
+}
+
+/** Returns true if the user has asked for animations to be minimised */
+export function prefers_reduced_motion() {
+  return window.matchMedia("(prefers-reduced-motion: reduce)").matches;
+}
+
+/** Returns true if the system color scheme is dark */
+export function prefers_dark() {
+  return window.matchMedia("(prefers-color-scheme: dark)").matches;
+}
+
+/** Returns false if the browser is known to be offline */
+export function is_online() {
+  return navigator.onLine;
+}
+
+/** Returns the browser's user agent string */
+export function user_agent() {
+  return navigator.userAgent;
+}
+
+/** Returns the platform the browser is running on, or an empty string if unknown */
+export function platform() {
+  // Tries to use expermental feature if avaliable.
+  try {
+    if (navigator.userAgentData.platform) {
+      return navigator.userAgentData.platform;
+    }
+  } catch { }
+
+  return navigator.platform || "";
+}
+
+/** Returns the preferred language of the browser, as a language tag such as "en-GB" */
+export function language() {
+  return navigator.language || "en";
+}
+
+/** Downloads the text contents as a file with the given name */
+export function download(filename, contents) {
+  const url = URL.createObjectURL(new Blob([contents], { type: "application/json" }));
+  const link = document.createElement("a");
+  link.href = url;
+  link.download = filename;
+  link.click();
+  URL.revokeObjectURL(url);
+}
+
+/** Copies the text to the clipboard, rejecting if the clipboard is unavailable */
+export async function copy_to_clipboard(text) {
+  await navigator.clipboard.writeText(text);
+}
+
+/** Opens a file picker, resolving to the picked file's text, or null if no file was picked */
+export function pick_file() {
+  return new Promise((resolve, reject) => {
+    const input = document.createElement("input");
+    input.type = "file";
+    input.accept = ".json,.ron";
+    input.onchange = () => {
+      const file = input.files[0];
+      if (!file) {
+        resolve(null);
+        return;
+      }
+      file.text().then(resolve, reject);
+    };
+    input.oncancel = () => resolve(null);
+    input.click();
+  });
+}
+
+/** Returns the full url of the page, including the fragment */
+export function href() {
+  return window.location.href;
+}
+
+/** Returns true if the browser supports the Web Share API */
+export function can_share() {
+  return typeof navigator.share === "function";
+}
+
+/** Opens the share sheet for the url, rejecting if sharing is cancelled or unavailable */
+export async function share(title, url) {
+  await navigator.share({ title, url });
+}
+
+/** Removes the loading splash, then dispatches a "tye_home:ready" event on the window */
+export function hide_splash() {
+  document.getElementById("loading_text")?.remove();
+  window.dispatchEvent(new Event("tye_home:ready"));
+}
+
+/** Replaces the loading splash with an error, or shows it over the app, then dispatches a "tye_home:error" event on the window */
+export function show_error(message) {
+  let splash = document.getElementById("loading_text");
+  if (!splash) {
+    // The splash is removed once the app has rendered, so it is recreated over the app.
+    splash = document.createElement("div");
+    splash.id = "loading_text";
+    splash.className = "centered";
+    splash.style.background = "rgba(0, 0, 0, 0.85)";
+    splash.style.padding = "16px";
+    document.body.append(splash);
+  }
+  splash.innerHTML = "";
+
+  const crashed = document.createElement("p");
+  crashed.textContent = "The app has crashed: " + message;
+  const safe_mode = document.createElement("p");
+  safe_mode.innerHTML = "<a href=\"?safe=1\">Start in safe mode</a>";
+  splash.append(crashed, safe_mode);
+  window.dispatchEvent(new CustomEvent("tye_home:error", { detail: message }));
+}
+
+/** Opens the url in a new tab */
+export function open_url(url) {
+  window.open(url, "_blank", "noopener");
+}
@@ -0,0 +1,20 @@
+//! Embeds information about the build, so the running build can be identified.
+
+use std::process::Command;
+
+fn main() {
+    // Builds outside of a git checkout, or without git installed, have no hash.
+    let hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+
+    if let Some(hash) = hash {
+        println!("cargo:rustc-env=TYE_HOME_GIT_HASH={}", hash.trim());
+    }
+
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs");
+}
@@ -1,14 +1,123 @@
-use std::sync::mpsc;
+use std::{collections::HashMap, sync::mpsc, time::Duration};
 
 use circular_queue::CircularQueue;
 
-use crate::{js_imports, LogType};
+use crate::{
+    compress,
+    console::{self, Command},
+    fetch::{self, Fetch, FetchState},
+    front_matter::{self, PageMeta},
+    home,
+    i18n::{self, tr, Lang},
+    idle::Idle,
+    js_imports,
+    logger::{self, LogFormat},
+    palette::{Action, Palette},
+    perf::Perf,
+    profile::{self, Profile},
+    throttle::Throttle,
+    tint,
+    toast::{self, Toast},
+    LogType,
+};
 
 /// Default storage key for my app.
 pub const STORAGE_KEY: &str = "tye_home";
 
 pub const LAYOUT_KEY: &str = "tye_home-Layout";
 
+/// The current version of the stored app state.
+pub const APP_VERSION: u32 = 2;
+
+/// Whether developer only features are enabled.
+pub const DEVELOPER_MODE: bool = cfg!(debug_assertions);
+
+/// The default number of logs kept in the log buffer.
+const DEFAULT_LOG_CAPACITY: usize = 16;
+/// The log buffer capacities that can be chosen.
+const LOG_CAPACITY_RANGE: std::ops::RangeInclusive<usize> = 1..=4096;
+
+/// The default length logs are shortened to in the log pane, in characters.
+const DEFAULT_LOG_DISPLAY_LEN: usize = 500;
+
+/// The most logs received from the logger each frame.
+const MAX_LOGS_PER_FRAME: usize = 256;
+
+/// The window width below which the mobile layout is used, in css pixels.
+const MOBILE_BREAKPOINT: f64 = 768.0;
+
+/// The height of the navigation buttons in the mobile layout, in points.
+const MOBILE_TAP_SIZE: f32 = 44.0;
+
+/// The window width below which the tablet layout is used, in css pixels.
+const TABLET_BREAKPOINT: f64 = 1024.0;
+
+/// How long the window width has to stay past the breakpoint before the layout switches, in seconds.
+const LAYOUT_DEBOUNCE: f64 = 0.5;
+
+/// How long the transition between pages takes, in seconds.
+const PAGE_TRANSITION: f32 = 0.15;
+
+/// How far pages slide in from during the transition, in points.
+const PAGE_TRANSITION_SLIDE: f32 = 8.0;
+
+/// The keys that switch to each page, in the order of [`Page::nav`].
+const PAGE_KEYS: [egui::Key; 9] = [
+    egui::Key::Num1,
+    egui::Key::Num2,
+    egui::Key::Num3,
+    egui::Key::Num4,
+    egui::Key::Num5,
+    egui::Key::Num6,
+    egui::Key::Num7,
+    egui::Key::Num8,
+    egui::Key::Num9,
+];
+
+/// The default zoom, as lower scales are too small on mobile.
+const DEFAULT_ZOOM: f32 = 1.2;
+
+/// The range the zoom can be set within.
+const ZOOM_RANGE: std::ops::RangeInclusive<f32> = 0.5..=3.0;
+
+/// How much the zoom buttons & shortcuts change the zoom by.
+const ZOOM_STEP: f32 = 0.1;
+
+/// Storage key for the theme the user has chosen.
+pub const THEME_KEY: &str = "tye_home-Theme";
+
+/// Storage key for the accent colour the user has chosen.
+pub const ACCENT_KEY: &str = "tye_home-Accent";
+
+/// Storage key for the last app version the user has seen.
+pub const VERSION_KEY: &str = "tye_home-Version";
+
+/// The markdown for the home page.
+const HOME: &str = include_str!("../assets/markdown/home.md");
+
+/// The German translation of the home page.
+const HOME_DE: &str = include_str!("../assets/markdown/home.de.md");
+
+/// The background tint of the home page.
+const HOME_TINT: egui::Color32 = egui::Color32::from_rgb(255, 170, 90);
+
+/// The changelog shown in the "What's new" window.
+const CHANGELOG: &str = include_str!("../assets/markdown/changelog.md");
+
+/// The documents that can be shown on the home page, as (name, markdown) pairs.
+///
+/// The first document is replaced by the remote home page, if there is one.
+const HOME_DOCS: &[(&str, &str)] = &[("Home", HOME), ("Changelog", CHANGELOG)];
+
+/// Where new issues with the app are filed.
+const ISSUE_URL: &str = "https://github.com/tye-exe/tye_home/issues/new";
+
+/// Exports larger than this need to be confirmed before downloading, in bytes.
+const EXPORT_WARN_SIZE: usize = 1024 * 1024;
+
+/// How many of the most recent errors are included in issue reports.
+const ISSUE_ERRORS: usize = 5;
+
 /// Creates the storage key for the given page.
 /// This is a macro due to ownership limitations.
 macro_rules! page_storage_key {
@@ -17,13 +126,6 @@ macro_rules! page_storage_key {
     };
 }
 
-/// Inputs a blank line.
-macro_rules! new_line {
-    ($ui:expr) => {
-        $ui.label("");
-    };
-}
-
 #[derive(serde::Deserialize, serde::Serialize, Debug)]
 #[serde(default)]
 /// Contains the data for the example page.
@@ -33,374 +135,2466 @@ pub struct Example {
 
     #[serde(skip)] // This how you opt-out of serialization of a field
     pub value: f32,
+
+    /// The background tint of the page.
+    pub tint: Option<egui::Color32>,
 }
 
 impl Default for Example {
     fn default() -> Self {
         Example {
             label: "Hello world!".to_owned(),
-            value: 3.1415926,
+            value: std::f32::consts::PI,
+            tint: None,
         }
     }
 }
 
-// Kinded generates a "kind" enum equivalent to this enum; similar to `ErrorKind`
-#[derive(serde::Deserialize, serde::Serialize, kinded::Kinded, Debug)]
-#[kinded(derive(serde::Deserialize, serde::Serialize), kind = Page)]
-/// The possible pages that can be displayed
-pub enum PageData {
-    Home,
-    Example(Example),
-}
+impl Example {
+    /// The current version of the stored example data.
+    const VERSION: u32 = 1;
 
-impl Default for PageData {
-    fn default() -> Self {
-        Self::Home
-    }
-}
+    /// The range the value slider allows.
+    const VALUE_RANGE: std::ops::RangeInclusive<f32> = 0.0..=10.0;
 
-impl PageData {
-    /// Saves the data from this page to storage.
-    pub fn save(&self, frame: &mut eframe::Frame) {
-        let page = self.kind();
-        log::debug!("Saving path: {}", page_storage_key!(page));
+    /// The most characters of the label kept in a link, so links stay short.
+    const MAX_LINK_LABEL: usize = 64;
 
-        match frame.storage_mut() {
-            Some(storage) => {
-                log::debug!("Saving data: {:?}", self);
-                eframe::set_value(storage, page_storage_key!(page), self);
+    /// Gets the query string that links to this example's label & value.
+    fn link_query(&self) -> String {
+        let label: String = self.label.chars().take(Self::MAX_LINK_LABEL).collect();
+        format!("label={}&value={}", url_encode(&label), self.value)
+    }
+
+    /// Sets the label & value from a query string made by [`Example::link_query`].
+    ///
+    /// Unknown or invalid parameters are ignored.
+    fn apply_link_query(&mut self, query: &str) {
+        for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+            match key {
+                "label" => {
+                    self.label = url_decode(value)
+                        .chars()
+                        .take(Self::MAX_LINK_LABEL)
+                        .collect();
+                }
+                "value" => match value.parse::<f32>() {
+                    Ok(value) if Self::VALUE_RANGE.contains(&value) => self.value = value,
+                    _ => log::warn!("Ignoring invalid example value in link: {value}"),
+                },
+                _ => log::warn!("Ignoring unknown example link parameter: {key}"),
             }
-            None => log::error!("Failed to save path: {}", page_storage_key!(page)),
         }
     }
-}
 
-impl Into<PageData> for Page {
-    /// Converts a [`Page`] into its respective default [`PageData`].
-    fn into(self) -> PageData {
-        match self {
-            Page::Home => PageData::Home,
-            Page::Example => PageData::Example(Default::default()),
+    /// Upgrades example data stored with an older version.
+    fn migrate(mut self, version: u32) -> Self {
+        if version < 1 {
+            // Version 0 is the un-versioned format, which has the same fields as version 1.
+            log::debug!("Migrating example data from version {version}.");
+        }
+
+        // Stored data may be corrupt or hand-edited.
+        let (min, max) = (*Self::VALUE_RANGE.start(), *Self::VALUE_RANGE.end());
+        if !Self::VALUE_RANGE.contains(&self.value) {
+            log::warn!("Example value {} is out of range, clamping it.", self.value);
+            self.value = match self.value.is_nan() {
+                true => Example::default().value,
+                false => self.value.clamp(min, max),
+            };
         }
+        self
     }
 }
 
-impl Page {
-    /// Creates a [`PageData`] instance from the stored values for this page.
-    ///
-    /// If no data exists then the default data is used instead.
-    pub fn load(self, frame: &mut eframe::Frame) -> PageData {
-        log::debug!("Loading path: {}", page_storage_key!(self));
+impl PageView for Example {
+    fn ui(&mut self, ui: &mut egui::Ui, _ctx: &egui::Context, _frame: &mut dyn Host) -> bool {
+        let mut changed = false;
 
-        match frame.storage() {
-            Some(storage) => {
-                let page_data =
-                    eframe::get_value(storage, page_storage_key!(self)).unwrap_or_default();
-                log::debug!("Loading data: {:?}", page_data);
-                page_data
-            }
-            None => self.into(),
+        // The central panel the region left after adding TopPanel's and SidePanel's
+        ui.heading("eframe template");
+
+        ui.horizontal(|ui| {
+            ui.label("Write something: ");
+            changed |= ui.text_edit_singleline(&mut self.label).changed();
+        });
+
+        let slider = ui.add(egui::Slider::new(&mut self.value, Example::VALUE_RANGE).text("value"));
+        changed |= slider.changed();
+        if ui.button("Increment").clicked() {
+            self.value += 1.0;
+            changed = true;
         }
+
+        ui.horizontal(|ui| {
+            let mut tinted = self.tint.is_some();
+            changed |= ui.checkbox(&mut tinted, "Background tint: ").changed();
+
+            match tinted {
+                true => {
+                    let colour = self.tint.get_or_insert(egui::Color32::DARK_BLUE);
+                    changed |= ui.color_edit_button_srgba(colour).changed();
+                }
+                false => self.tint = None,
+            }
+        });
+
+        ui.separator();
+
+        ui.add(egui::github_link_file!(
+            "https://github.com/emilk/eframe_template/blob/main/",
+            "Source code."
+        ));
+
+        // The page is in a scroll area, which has no bottom to anchor to.
+        // So the footer fills the rest of the visible area, or goes after the content if it doesn't fit.
+        let remaining = ui.clip_rect().bottom() - ui.cursor().top();
+        let min_height = ui.spacing().interact_size.y * 3.0;
+        let size = egui::vec2(ui.available_width(), remaining.max(min_height));
+        ui.allocate_ui_with_layout(size, egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
+            powered_by_egui_and_eframe(ui);
+            egui::warn_if_debug_build(ui);
+        });
+
+        changed
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, kinded::Kinded, Debug)]
-#[kinded(kind = Layout)]
-/// The different layouts that the app could have.
-pub enum LayoutData {
-    Desktop {},
-    Mobile { tabs_open: bool },
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[serde(default)]
+/// Contains the data for the settings page.
+pub struct Settings {
+    /// The theme to display the app with.
+    pub theme: egui::ThemePreference,
+    /// The colour of selections & links, or none for the theme's colour.
+    pub accent: Option<egui::Color32>,
+    /// How many logs the log buffer keeps.
+    pub log_capacity: usize,
+    /// The layout to start with, or none to follow the window width.
+    pub default_layout: Option<Layout>,
+    /// The language the UI is displayed in.
+    pub lang: Lang,
 }
 
-impl Default for LayoutData {
+impl Default for Settings {
     fn default() -> Self {
-        Self::Desktop {}
+        Settings {
+            theme: egui::ThemePreference::System,
+            accent: None,
+            log_capacity: DEFAULT_LOG_CAPACITY,
+            default_layout: None,
+            lang: Lang::default(),
+        }
     }
 }
 
-// We derive Deserialize/Serialize so we can persist app state on shutdown.
-#[derive(serde::Deserialize, serde::Serialize)]
-#[serde(default)]
-/// Contains the current in-memory data for my app.
-pub struct MyApp {
-    /// The data for the currently rendered page.
-    page_data: PageData,
+impl PageView for Settings {
+    /// Displays the settings form, returning true if any setting was changed.
+    fn ui(&mut self, ui: &mut egui::Ui, _ctx: &egui::Context, _frame: &mut dyn Host) -> bool {
+        let mut changed = false;
 
-    /// Whether the debug window is open.
-    debug_window: bool,
+        ui.heading(tr("settings.heading"));
 
-    /// Which layout to render.
-    layout: LayoutData,
+        ui.horizontal(|ui| {
+            ui.label(tr("settings.language"));
+            for lang in Lang::ALL {
+                changed |= ui
+                    .selectable_value(&mut self.lang, lang, lang.to_string())
+                    .changed();
+            }
+        });
 
-    #[serde(skip)]
-    /// A buffer of the 'x' most recent logs.
-    logs: CircularQueue<String>,
-    #[serde(skip)]
-    /// Receives log messages to display.
-    log_receiver: Option<mpsc::Receiver<LogType>>,
-}
+        ui.horizontal(|ui| {
+            ui.label(tr("settings.theme"));
+            for (theme, key) in [
+                (egui::ThemePreference::System, "settings.theme.system"),
+                (egui::ThemePreference::Light, "settings.theme.light"),
+                (egui::ThemePreference::Dark, "settings.theme.dark"),
+            ] {
+                changed |= ui
+                    .selectable_value(&mut self.theme, theme, tr(key))
+                    .changed();
+            }
+        });
 
-impl Default for MyApp {
-    fn default() -> Self {
-        Self {
-            page_data: PageData::Home,
-            debug_window: false,
-            layout: LayoutData::Desktop {},
-            logs: CircularQueue::with_capacity(16),
-            log_receiver: None,
-        }
+        ui.horizontal(|ui| {
+            let mut custom = self.accent.is_some();
+            changed |= ui.checkbox(&mut custom, tr("settings.accent")).changed();
+
+            match custom {
+                true => {
+                    let accent = self
+                        .accent
+                        .get_or_insert(egui::Color32::from_rgb(255, 170, 90));
+                    changed |= ui.color_edit_button_srgba(accent).changed();
+                }
+                false => self.accent = None,
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(tr("settings.log_capacity"));
+            let capacity_input =
+                ui.add(egui::DragValue::new(&mut self.log_capacity).range(LOG_CAPACITY_RANGE));
+            changed |= capacity_input.changed();
+        });
+
+        ui.horizontal(|ui| {
+            ui.label(tr("settings.default_layout"));
+            for (layout, label) in [
+                (None, "Automatic"),
+                (Some(Layout::Desktop), "Desktop"),
+                (Some(Layout::Tablet), "Tablet"),
+                (Some(Layout::Mobile), "Mobile"),
+            ] {
+                changed |= ui
+                    .selectable_value(&mut self.default_layout, layout, label)
+                    .changed();
+            }
+        });
+
+        changed
     }
 }
 
-impl MyApp {
-    /// Gets the [`Page`] that the current [`PageData`] represents.
-    pub fn page(&self) -> Page {
-        self.page_data.kind()
+/// Provides the storage the app is saved to, which is eframe's frame while running.
+///
+/// eframe's frame can't be created outside of eframe, so tests provide their own host.
+pub trait Host {
+    /// Gets the storage, if it is available.
+    fn storage(&self) -> Option<&dyn eframe::Storage>;
+
+    /// Gets the storage to modify, if it is available.
+    fn storage_mut(&mut self) -> Option<&mut (dyn eframe::Storage + 'static)>;
+}
+
+impl Host for eframe::Frame {
+    fn storage(&self) -> Option<&dyn eframe::Storage> {
+        eframe::Frame::storage(self)
     }
 
-    /// Gets the [`Layout`] that the current [`LayoutData`] represents.
-    pub fn layout(&self) -> Layout {
-        self.layout.kind()
+    fn storage_mut(&mut self) -> Option<&mut (dyn eframe::Storage + 'static)> {
+        eframe::Frame::storage_mut(self)
     }
+}
 
-    /// Saves the current [`PageData`] & loads the [`PageData`] for the given [`Page`].
-    pub fn switch_page(&mut self, page: Page, frame: &mut eframe::Frame) {
-        self.page_data.save(frame);
-        self.page_data = page.load(frame);
+/// Renders the contents of a page, from the page's data.
+///
+/// The data of built in pages is still stored as a [`PageData`], this only moves the rendering out of
+/// [`MyApp::show`]. Other pages can be added with [`MyApp::register_page`].
+pub trait PageView {
+    /// Displays the page, returning true if its data was changed by the user.
+    fn ui(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, frame: &mut dyn Host) -> bool;
+
+    /// Gets the data to store for a registered page, if it has any.
+    ///
+    /// Built in pages are stored as a [`PageData`] instead.
+    fn store(&self) -> Option<String> {
+        None
     }
+
+    /// Restores a registered page from the data returned by [`PageView::store`].
+    fn restore(&mut self, _data: &str) {}
 }
 
-#[derive(thiserror::Error, Debug)]
-pub enum InitError {
-    #[error("Unable to access storage.")]
-    StorageError(),
+/// Displays the home page, which is rendered from the app's state as it has no page data.
+struct HomeView<'a> {
+    /// Whether the browser is online.
+    online: bool,
+    /// The index of the document shown, from [`HOME_DOCS`].
+    doc: &'a mut usize,
+    /// The home page fetched from a url, if there is one.
+    remote: Option<&'a str>,
+    md_cache: &'a mut egui_commonmark::CommonMarkCache,
+    profile: &'a Fetch<Profile>,
+    /// When the profile was last requested, in seconds since the app started.
+    profile_requested: &'a mut f64,
 }
 
-impl MyApp {
-    /// Called once before the first frame.
-    pub fn new(
-        cc: &eframe::CreationContext<'_>,
-        log_receiver: Option<mpsc::Receiver<LogType>>,
-    ) -> Result<Self, InitError> {
-        // This is also where you can customize the look and feel of egui using
-        // `cc.egui_ctx.set_visuals` and `cc.egui_ctx.set_fonts`.
+impl PageView for HomeView<'_> {
+    fn ui(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, _frame: &mut dyn Host) -> bool {
+        if !self.online {
+            ui.colored_label(ui.visuals().warn_fg_color, tr("home.offline"));
+            ui.separator();
+        }
 
-        // Lower scale is too small on mobile.
-        match js_imports::is_mobile() {
-            true => cc.egui_ctx.set_pixels_per_point(1.2),
-            false => cc.egui_ctx.set_pixels_per_point(1.2),
+        if HOME_DOCS.len() > 1 {
+            ui.horizontal(|ui| {
+                for (index, (name, _)) in HOME_DOCS.iter().enumerate() {
+                    ui.selectable_value(self.doc, index, *name);
+                }
+            });
+            ui.separator();
         }
 
-        async fn fun_name() -> Result<(), Box<dyn std::error::Error>> {
-            let response =
-                reqwest::get("https://discordlookup.mesalytic.moe/v1/user/1192519637448011827")
-                    .await?
-                    .text()
-                    .await?;
-            let response: serde_json::Value = serde_json::from_str(&response)?;
+        // The embedded page is used until the remote page is available.
+        let markdown = match (*self.doc, self.remote) {
+            (0, Some(markdown)) => markdown,
+            (0, None) if i18n::lang() == Lang::German => front_matter::parse(HOME_DE).1,
+            (doc, _) => {
+                let (_, markdown) = HOME_DOCS.get(doc).unwrap_or(&HOME_DOCS[0]);
+                front_matter::parse(markdown).1
+            }
+        };
 
-            log::debug!("pfp: {}", response["raw"]["global_name"]);
-            // log::debug!("pfp: {}", response["raw"][""]);
-            // egui::include_image!()
-            // let uri = response["avatar"]["link"].as_str().ok_or(EmptyError())?;
-            // egui::Image::from_uri(uri).rounding(0.5);
+        egui_commonmark::CommonMarkViewer::new().show(ui, self.md_cache, markdown);
 
-            Ok(())
+        ui.separator();
+        // Debounced, so rapid clicks don't spam the lookup api.
+        let now = ui.input(|i| i.time);
+        let can_refresh = self.online && now - *self.profile_requested >= profile::REFRESH_COOLDOWN;
+        if profile::show(ui, self.profile.state(), can_refresh) {
+            profile::spawn_fetch(self.profile.sender(), ctx.clone());
+            *self.profile_requested = now;
         }
+        false
+    }
+}
 
-        wasm_bindgen_futures::spawn_local(async {
-            fun_name().await;
-        });
-
-        // let response = reqwest::blocking::
-        // log::debug!()
+/// Displays the page shown for links to pages that don't exist.
+struct NotFoundView<'a> {
+    /// The page that was linked to.
+    attempted: &'a str,
+    /// Set when the user asks to go to the home page.
+    go_home: &'a mut bool,
+}
 
-        // Load previous app state (if any).
-        // let mut app: MyApp = cc
-        //     .storage
-        //     .and_then(|storage| eframe::get_value::<_>(storage, STORAGE_KEY))
-        //     .map(|mut app: MyApp| {app.log_receiver = log_receiver; app})
-        //     .unwrap_or_default();
+impl PageView for NotFoundView<'_> {
+    fn ui(&mut self, ui: &mut egui::Ui, _ctx: &egui::Context, _frame: &mut dyn Host) -> bool {
+        ui.heading("Page not found");
+        ui.label(format!("There is no page called \"{}\".", self.attempted));
+        *self.go_home |= ui.link("Go to the home page").clicked();
+        false
+    }
+}
 
-        // app.layout = match js_imports::is_mobile() {
-        //     true => LayoutData::Mobile { tabs_open:  },
-        //     false => LayoutData::Desktop {  },
-        // }
+/// A page registered at runtime, with [`MyApp::register_page`].
+struct DynamicPage {
+    /// The label in the navigation, which the storage key is derived from.
+    name: String,
+    view: Box<dyn PageView>,
+}
 
-        // app
+impl DynamicPage {
+    /// Gets the storage key for the page's data.
+    fn storage_key(&self) -> String {
+        format!("{STORAGE_KEY}-Registered-{}", self.name)
+    }
+}
 
-        let storage = cc.storage.ok_or(InitError::StorageError())?;
-        let mut app = eframe::get_value(storage, STORAGE_KEY).unwrap_or_else(|| {
-            let layout =
-                eframe::get_value(storage, LAYOUT_KEY).unwrap_or_else(
-                    || match js_imports::is_mobile() {
-                        true => LayoutData::Mobile { tabs_open: false },
-                        false => LayoutData::Desktop {},
-                    },
-                );
-            let mut app = MyApp::default();
-            app.layout = layout;
-            app
-        });
+// Kinded generates a "kind" enum equivalent to this enum; similar to `ErrorKind`
+#[derive(serde::Deserialize, serde::Serialize, kinded::Kinded, Debug, Default)]
+#[kinded(derive(Hash, serde::Deserialize, serde::Serialize), kind = Page)]
+/// The possible pages that can be displayed
+pub enum PageData {
+    #[default]
+    Home,
+    Example(Example),
+    Settings(Settings),
+    /// Shown when a link is to a page that doesn't exist.
+    NotFound {
+        /// The page that was linked to.
+        attempted: String,
+    },
+}
 
-        app.log_receiver = log_receiver;
+#[derive(serde::Deserialize, serde::Serialize)]
+/// Stored page data, alongside the version it was stored with.
+struct Versioned<T> {
+    version: u32,
+    data: T,
+}
 
-        Ok(app)
+impl PageData {
+    /// The current version of the stored data for this page.
+    fn version(&self) -> u32 {
+        match self {
+            PageData::Home => 0,
+            PageData::Example(_) => Example::VERSION,
+            PageData::Settings(_) => 0,
+            PageData::NotFound { .. } => 0,
+        }
     }
-}
 
-impl eframe::App for MyApp {
-    fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        eframe::set_value(storage, STORAGE_KEY, self);
+    /// Gets the background tint for this page, if any.
+    pub fn tint(&self) -> Option<egui::Color32> {
+        match self {
+            PageData::Home => Some(HOME_TINT),
+            PageData::Example(example) => example.tint,
+            PageData::Settings(_) => None,
+            PageData::NotFound { .. } => None,
+        }
     }
 
-    /// Called each time the UI needs repainting, which may be many times per second.
-    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
-        // Put your widgets into a `SidePanel`, `TopBottomPanel`, `CentralPanel`, `Window` or `Area`.
-        // For inspiration and more examples, go to https://emilk.github.io/egui
+    /// Upgrades page data stored with an older version.
+    fn migrate(self, version: u32) -> Self {
+        match self {
+            PageData::Home => PageData::Home,
+            PageData::Example(example) => PageData::Example(example.migrate(version)),
+            PageData::Settings(settings) => PageData::Settings(settings),
+            PageData::NotFound { attempted } => PageData::NotFound { attempted },
+        }
+    }
 
-        // Differences between mobile & desktop site in one place.
-        // let theme_buttons: Box<dyn Fn(&mut egui::Ui)>;
-        // let pages: Box<dyn Fn(&mut egui::Ui, &egui::Context, &mut eframe::Frame, &mut MyApp)>;
-
-        // match self.layout() {
-        //     Layout::Desktop => {
-        //         theme_buttons = Box::new(|ui| {
-        //             egui::widgets::global_dark_light_mode_buttons(ui);
-        //         });
-        //         pages = Box::new(|ui, _, frame, app| {
-        //             let home_button =
-        //                 ui.add(egui::Button::new("Home").selected(app.page() == Page::Home));
-        //             let example_button =
-        //                 ui.add(egui::Button::new("Example").selected(app.page() == Page::Example));
-        //             let debug_menu =
-        //                 ui.add(egui::Button::new("Debug Menu").selected(app.debug_window));
-
-        //             if home_button.clicked() {
-        //                 app.switch_page(Page::Home, frame);
-        //             }
-        //             if example_button.clicked() {
-        //                 app.switch_page(Page::Example, frame);
-        //             }
-        //             if debug_menu.clicked() {
-        //                 app.debug_window = !app.debug_window;
-        //             }
-        //         })
-        //     }
-        //     Layout::Mobile => {
-        //         theme_buttons = Box::new(|ui| {
-        //             egui::widgets::global_dark_light_mode_switch(ui);
-        //         });
-        //         pages = Box::new(|ui, ctx, frame, app| {
-        //             get_mobile!(app, {
-        //                 let page_button = ui.add(egui::Button::new("Pages").selected(tabs_open));
-        //                 if page_button.clicked() {
-        //                     tabs_open = !tabs_open;
-        //                     egui::Window::new("Pages").show(ctx, |ui| {
-        //                         //
-        //                     });
-        //                 }
-        //             });
-        //         })
-        //     }
-        // }
+    /// Saves the data from this page to storage.
+    pub fn save(&self, frame: &mut dyn Host) -> Result<(), SaveError> {
+        self.save_to(frame.storage_mut())
+    }
 
-        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
-            // The top panel is often a good place for a menu bar:
+    /// Saves the data from this page to the given storage.
+    pub fn save_to(
+        &self,
+        storage: Option<&mut (dyn eframe::Storage + 'static)>,
+    ) -> Result<(), SaveError> {
+        let page = self.kind();
+        // There is nothing to save for a missing page.
+        if page == Page::NotFound {
+            return Ok(());
+        }
+        log::debug!("Saving path: {}", page_storage_key!(page));
 
-            egui::menu::bar(ui, |ui| {
-                match self.layout() {
-                    Layout::Desktop => egui::widgets::global_dark_light_mode_buttons(ui),
-                    Layout::Mobile => egui::widgets::global_dark_light_mode_switch(ui),
-                }
+        let Some(storage) = storage else {
+            log::warn!("Failed to save path: {}", page_storage_key!(page));
+            return Err(SaveError::StorageUnavailable(page));
+        };
 
-                ui.add(egui::Separator::default().vertical());
+        log::debug!("Saving data: {:?}", self);
+        let versioned = Versioned {
+            version: self.version(),
+            data: self,
+        };
 
-                match self.layout {
-                    LayoutData::Desktop {} => {
-                        let home_button =
-                            ui.add(egui::Button::new("Home").selected(self.page() == Page::Home));
-                        let example_button = ui.add(
-                            egui::Button::new("Example").selected(self.page() == Page::Example),
-                        );
+        if !cfg!(feature = "compress-pages") {
+            eframe::set_value(storage, page_storage_key!(page), &versioned);
+            return Ok(());
+        }
 
-                        ui.separator();
+        let json = serde_json::to_string(&versioned).map_err(|e| {
+            log::warn!("Failed to serialize page data: {e}");
+            SaveError::Serialize(page, e)
+        })?;
+        storage.set_string(page_storage_key!(page), compress::compress(&json));
+        Ok(())
+    }
+}
 
-                        let debug_menu =
-                            ui.add(egui::Button::new("Debug Menu").selected(self.debug_window));
+/// The format app state is exported in.
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    /// Easier to edit by hand than json.
+    Ron,
+}
 
-                        if home_button.clicked() {
-                            self.switch_page(Page::Home, frame);
-                        }
-                        if example_button.clicked() {
-                            self.switch_page(Page::Example, frame);
-                        }
-                        if debug_menu.clicked() {
-                            self.debug_window = !self.debug_window;
-                        }
-                    }
-                    LayoutData::Mobile { ref mut tabs_open } => {
-                        let page_button = ui.add(egui::Button::new("Pages").selected(*tabs_open));
-                        if page_button.clicked() {
-                            *tabs_open = !*tabs_open;
-                        }
+impl ExportFormat {
+    /// All the formats, in the order they are listed.
+    pub const ALL: [ExportFormat; 2] = [ExportFormat::Json, ExportFormat::Ron];
 
-                        if *tabs_open {
-                            egui::Window::new("Pages").show(ctx, |ui| {
-                                ui.vertical(|ui| {
-                                    let home_button = ui.add(
-                                        egui::Button::new("Home")
-                                            .selected(self.page() == Page::Home),
-                                    );
-                                    let example_button = ui.add(
-                                        egui::Button::new("Example")
-                                            .selected(self.page() == Page::Example),
-                                    );
-
-                                    ui.separator();
-
-                                    let debug_menu = ui.add(
-                                        egui::Button::new("Debug Menu").selected(self.debug_window),
-                                    );
-
-                                    if home_button.clicked() {
-                                        self.switch_page(Page::Home, frame);
-                                    }
-                                    if example_button.clicked() {
-                                        self.switch_page(Page::Example, frame);
-                                    }
-                                    if debug_menu.clicked() {
-                                        self.debug_window = !self.debug_window;
-                                    }
-                                });
-                            });
-                        }
-                    }
-                }
-            });
-        });
+    /// Gets the name of exported state files in this format.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "tye_home-state.json",
+            ExportFormat::Ron => "tye_home-state.ron",
+        }
+    }
+}
 
-        if self.debug_window {
-            egui::Window::new("Debug window").show(ctx, |ui| {
-                let debug_page = ui.add(egui::Button::new("Debug Page"));
-                if debug_page.clicked() {
-                    log::info!("Page: {}\nPageData: {:?}", self.page(), self.page_data);
-                }
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Json => write!(f, "JSON"),
+            ExportFormat::Ron => write!(f, "RON"),
+        }
+    }
+}
 
-                let reset_storage = ui.add(egui::Button::new("Reset Page"));
-                if reset_storage.clicked() {
-                    // Overwrites the page saved data with default values.
-                    for page in Page::all().to_owned() {
-                        let page_data: PageData = page.into();
-                        page_data.save(frame);
-                    }
+#[derive(thiserror::Error, Debug)]
+pub enum SaveError {
+    #[error("Unable to save the {0} page, as storage is unavailable.")]
+    StorageUnavailable(Page),
+    #[error("Unable to save the {0} page: {1}")]
+    Serialize(Page, serde_json::Error),
+}
 
-                    // Sets the current page to its default.
-                    self.page_data = self.page().load(frame);
-                }
+impl From<Page> for PageData {
+    /// Converts a [`Page`] into its respective default [`PageData`].
+    fn from(page: Page) -> Self {
+        match page {
+            Page::Home => PageData::Home,
+            Page::Example => PageData::Example(Default::default()),
+            Page::Settings => PageData::Settings(Default::default()),
+            Page::NotFound => PageData::NotFound {
+                attempted: String::new(),
+            },
+        }
+    }
+}
 
-                ui.separator();
-                ui.label("Layout Options:");
+impl Page {
+    /// Gets the metadata for this page.
+    pub fn meta(self) -> PageMeta {
+        match self {
+            Page::Home => front_matter::parse(HOME).0,
+            Page::Example => PageMeta {
+                title: Some("Example".to_owned()),
+                order: 1,
+                ..Default::default()
+            },
+            Page::Settings => PageMeta {
+                title: Some("Settings".to_owned()),
+                order: 2,
+                ..Default::default()
+            },
+            Page::NotFound => PageMeta {
+                title: Some("Not Found".to_owned()),
+                hidden: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Gets the name the page is shown with, when it has no translation or title.
+    pub fn label(self) -> &'static str {
+        match self {
+            Page::Home => "Home",
+            Page::Example => "Example",
+            Page::Settings => "Settings",
+            Page::NotFound => "Not Found",
+        }
+    }
+
+    /// Gets the icon the page is shown with in the navigation.
+    pub fn icon(self) -> &'static str {
+        match self {
+            Page::Home => "🏠",
+            Page::Example => "✏",
+            Page::Settings => "⚙",
+            Page::NotFound => "❓",
+        }
+    }
+
+    /// Gets the pages shown in the navigation, in order.
+    ///
+    /// Draft & hidden pages are only shown in developer mode.
+    pub fn nav() -> Vec<(Page, PageMeta)> {
+        let mut pages: Vec<_> = Page::all()
+            .iter()
+            .copied()
+            // The missing page can't be navigated to directly.
+            .filter(|page| *page != Page::NotFound)
+            .map(|page| (page, page.meta()))
+            .filter(|(_, meta)| DEVELOPER_MODE || !(meta.draft || meta.hidden))
+            .collect();
+        pages.sort_by_key(|(_, meta)| meta.order);
+        pages
+    }
+
+    /// Gets the url fragment that links to this page.
+    pub fn fragment(self) -> String {
+        self.to_string().to_lowercase()
+    }
+
+    /// Gets the page that the url fragment links to.
+    ///
+    /// Returns `None` for unknown fragments.
+    pub fn from_fragment(fragment: &str) -> Option<Page> {
+        let page = Page::all()
+            .iter()
+            .copied()
+            .filter(|page| *page != Page::NotFound)
+            .find(|page| page.fragment() == fragment.to_lowercase());
+
+        if page.is_none() {
+            log::warn!("Unknown page fragment: {fragment}");
+        }
+        page
+    }
+
+    /// Overwrites the stored data for this page with its default data.
+    pub fn reset(self, frame: &mut dyn Host) -> Result<(), SaveError> {
+        let page_data: PageData = self.into();
+        page_data.save(frame)
+    }
+
+    /// Creates a [`PageData`] instance from the stored values for this page.
+    ///
+    /// If no data exists then the default data is used instead.
+    /// Corrupt data is backed up before the defaults are used, as they will overwrite it.
+    pub fn load(self, frame: &mut dyn Host) -> PageData {
+        let (page_data, corrupt) = self.load_checked(frame.storage());
+        if let (Some(corrupt), Some(storage)) = (corrupt, frame.storage_mut()) {
+            corrupt.back_up(storage);
+        }
+        page_data
+    }
+
+    /// Creates a [`PageData`] instance from the stored values for this page in the given storage.
+    ///
+    /// If no data exists, or it is corrupt, then the default data is used instead.
+    pub fn load_from(self, storage: Option<&dyn eframe::Storage>) -> PageData {
+        self.load_checked(storage).0
+    }
+
+    /// Creates a [`PageData`] instance from the stored values for this page in the given storage.
+    ///
+    /// If the data is corrupt, it is returned so it can be backed up before being overwritten.
+    fn load_checked(
+        self,
+        storage: Option<&dyn eframe::Storage>,
+    ) -> (PageData, Option<CorruptData>) {
+        log::debug!("Loading path: {}", page_storage_key!(self));
+
+        let (page_data, corrupt) = match storage.map(|storage| self.read(storage)) {
+            Some(Ok(Some(page_data))) => (page_data, None),
+            Some(Err(raw)) => (
+                self.into(),
+                Some(CorruptData {
+                    key: format!("{STORAGE_KEY}-{self}-Corrupt"),
+                    raw,
+                }),
+            ),
+            _ => (self.into(), None),
+        };
+        log::debug!("Loading data: {:?}", page_data);
+        (page_data, corrupt)
+    }
+
+    /// Reads the stored data for this page.
+    ///
+    /// Returns `Ok(None)` if no data is stored, or the raw stored value if it is corrupt.
+    fn read(self, storage: &dyn eframe::Storage) -> Result<Option<PageData>, String> {
+        let Some(raw) = storage.get_string(page_storage_key!(self)) else {
+            return Ok(None);
+        };
+
+        // Compressed data is stored as json, while uncompressed data is stored as ron.
+        let parsed = match compress::decompress(&raw) {
+            Some(json) => serde_json::from_str::<Versioned<PageData>>(&json)
+                .map(|Versioned { version, data }| data.migrate(version))
+                .map_err(|e| e.to_string()),
+            None => ron::from_str::<Versioned<PageData>>(&raw)
+                .map(|Versioned { version, data }| data.migrate(version))
+                // Un-versioned data is treated as version 0.
+                .or_else(|_| ron::from_str::<PageData>(&raw).map(|data| data.migrate(0)))
+                .map_err(|e| e.to_string()),
+        };
+
+        match parsed {
+            Ok(page_data) => Ok(Some(page_data)),
+            Err(e) => {
+                log::warn!(
+                    "Stored data at {} is corrupt, so the defaults are used: {e}",
+                    page_storage_key!(self)
+                );
+                Err(raw)
+            }
+        }
+    }
+}
+
+/// Stored page data that couldn't be read, which is backed up before the defaults overwrite it.
+#[derive(Debug)]
+struct CorruptData {
+    /// The key the data is backed up to.
+    key: String,
+    /// The stored value.
+    raw: String,
+}
+
+impl CorruptData {
+    /// Stores the corrupt data under its backup key.
+    fn back_up(self, storage: &mut dyn eframe::Storage) {
+        log::warn!("Backed up the corrupt data to: {}", self.key);
+        storage.set_string(&self.key, self.raw);
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, kinded::Kinded, Debug)]
+#[kinded(derive(serde::Deserialize, serde::Serialize), kind = Layout)]
+/// The different layouts that the app could have.
+pub enum LayoutData {
+    Desktop {},
+    Tablet {},
+    Mobile {},
+}
+
+impl Default for LayoutData {
+    fn default() -> Self {
+        Self::Desktop {}
+    }
+}
+
+impl From<Layout> for LayoutData {
+    /// Converts a [`Layout`] into its respective default [`LayoutData`].
+    fn from(layout: Layout) -> Self {
+        match layout {
+            Layout::Desktop => LayoutData::Desktop {},
+            Layout::Tablet => LayoutData::Tablet {},
+            Layout::Mobile => LayoutData::Mobile {},
+        }
+    }
+}
+
+impl Layout {
+    /// Gets the layout suited to the given window width, in css pixels.
+    pub fn for_width(width: f64) -> Layout {
+        match width {
+            width if width < MOBILE_BREAKPOINT => Layout::Mobile,
+            width if width < TABLET_BREAKPOINT => Layout::Tablet,
+            _ => Layout::Desktop,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Default)]
+#[serde(default)]
+/// Which of the floating windows are open.
+pub struct WindowState {
+    /// Whether the debug window is open.
+    pub debug: bool,
+    /// Whether the console overlay is open.
+    pub console: bool,
+    /// Whether the frame time overlay is shown.
+    pub perf: bool,
+}
+
+impl WindowState {
+    /// Displays a checkbox for each window, to open or close it.
+    fn menu_ui(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.debug, "Debug Window");
+        ui.checkbox(&mut self.console, "Console");
+        ui.checkbox(&mut self.perf, "Frame Time");
+    }
+}
+
+#[derive(serde::Serialize, Debug)]
+/// A summary of the app's state, for debugging.
+pub struct AppSnapshot {
+    /// The page currently being displayed.
+    pub page: Page,
+    /// The layout currently being used.
+    pub layout: Layout,
+    /// Whether the debug window is open.
+    pub debug_window: bool,
+    /// The number of logs in the log buffer.
+    pub log_count: usize,
+    /// Whether logs are being received from the logger.
+    pub receiving_logs: bool,
+}
+
+// We derive Deserialize/Serialize so we can persist app state on shutdown.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+/// Contains the current in-memory data for my app.
+pub struct MyApp {
+    /// The version of the stored app state.
+    version: u32,
+
+    /// The data for the currently rendered page.
+    page_data: PageData,
+    #[serde(skip)]
+    /// Whether the current page has changes that haven't been written to its storage.
+    dirty: bool,
+    #[serde(skip)]
+    /// Whether the url no longer links to the current page's state.
+    link_outdated: bool,
+    #[serde(skip)]
+    /// Whether the app has been rendered, so the loading splash was hidden.
+    rendered: bool,
+    #[serde(skip)]
+    /// Pages registered at runtime, with [`MyApp::register_page`].
+    dynamic_pages: Vec<DynamicPage>,
+    #[serde(skip)]
+    /// The index of the registered page being displayed, instead of the [`PageData`] page.
+    dynamic_page: Option<usize>,
+
+    /// Which floating windows are open.
+    windows: WindowState,
+    /// Whether the debug window stays open across visits, otherwise it starts closed.
+    remember_debug: bool,
+    #[serde(skip)]
+    /// Lists actions to run from the keyboard.
+    palette: Palette,
+    #[serde(skip)]
+    /// Whether the debug window was open before `?debug=1` opened it, which is what is stored.
+    stored_debug: Option<bool>,
+    /// The scale of the UI, in pixels per point.
+    zoom: f32,
+    #[serde(skip)]
+    /// The command being typed into the console.
+    console_input: String,
+
+    /// Which layout to render.
+    layout: LayoutData,
+    /// Whether the layout switches with the window width.
+    auto_layout: bool,
+    #[serde(skip)]
+    /// A layout to render instead of the stored layout, for testing.
+    force_layout: Option<Layout>,
+    #[serde(skip)]
+    /// The layout the window width is switching to, & when the width crossed the breakpoint.
+    layout_pending: Option<(Layout, f64)>,
+
+    /// The idle timeout options.
+    idle: Idle,
+
+    /// How far down each page was scrolled, in points.
+    scroll_offsets: HashMap<Page, f32>,
+    #[serde(skip)]
+    /// The scroll offset to apply to the page on the next frame.
+    restore_scroll: Option<f32>,
+
+    /// The url to fetch the home page markdown from, instead of using the embedded page.
+    home_url: Option<String>,
+    #[serde(skip)]
+    /// The markdown fetched from the home url.
+    home_markdown: Fetch<String>,
+    /// The index of the document shown on the home page, from [`HOME_DOCS`].
+    home_doc: usize,
+    #[serde(skip)]
+    /// Caches the rendering of all markdown, so it isn't recreated every frame.
+    md_cache: egui_commonmark::CommonMarkCache,
+
+    /// How many logs the log buffer keeps.
+    log_capacity: usize,
+    #[serde(skip)]
+    /// A buffer of the 'x' most recent logs.
+    logs: CircularQueue<LogType>,
+    /// How logs are formatted in the log pane.
+    log_format: LogFormat,
+    /// Whether long logs are wrapped, instead of scrolled horizontally.
+    log_wrap: bool,
+    /// Whether blank lines and trailing whitespace are removed from logs.
+    log_compact: bool,
+    /// Whether the log pane follows the newest logs as they arrive.
+    ///
+    /// Otherwise the log pane stays on the logs being read.
+    log_autoscroll: bool,
+    #[serde(skip)]
+    /// The scroll offset of the log pane on the last frame.
+    log_scroll_offset: f32,
+    #[serde(skip)]
+    /// The id of the next log, when the log pane was last displayed.
+    log_seen_id: usize,
+    /// The length logs are shortened to in the log pane, in characters.
+    log_display_len: usize,
+    /// The most verbose level of logs shown in the log pane.
+    display_filter: log::LevelFilter,
+    #[serde(skip)]
+    /// Only logs containing this are shown in the log pane.
+    log_search: String,
+    #[serde(skip)]
+    /// Receives log messages to display.
+    log_receiver: Option<mpsc::Receiver<LogType>>,
+    #[serde(skip)]
+    /// Whether the warning that the logger couldn't be installed is shown.
+    logger_warning: bool,
+    #[serde(skip)]
+    /// Whether the "What's new" window is open.
+    whats_new: bool,
+    #[serde(skip)]
+    /// My Discord profile.
+    profile: Fetch<Profile>,
+    #[serde(skip)]
+    /// When the profile was last requested, in seconds since the app started.
+    profile_requested: f64,
+    #[serde(skip)]
+    /// The theme the user has chosen, if any.
+    theme: Option<egui::ThemePreference>,
+    #[serde(skip)]
+    /// The accent colour the user has chosen, if any.
+    accent: Option<egui::Color32>,
+    /// The language the user has chosen, or none to follow the browser.
+    lang: Option<Lang>,
+    #[serde(skip)]
+    /// The id the next received log will have.
+    next_log_id: usize,
+    #[serde(skip)]
+    /// The levels being logged, which received logs are filtered by.
+    log_level: log::LevelFilter,
+    #[serde(skip)]
+    /// The notifications currently being displayed.
+    toasts: Vec<Toast>,
+    #[serde(skip)]
+    /// Whether the reload confirmation window is open.
+    confirm_reload: bool,
+    #[serde(skip)]
+    /// Whether the reset confirmation window is open.
+    confirm_reset: bool,
+    #[serde(skip)]
+    /// An export too large to download without confirmation.
+    confirm_export: Option<(ExportFormat, String)>,
+    /// The format app state is exported in.
+    export_format: ExportFormat,
+    #[serde(skip)]
+    /// Throttles repaints while nothing is happening.
+    throttle: Throttle,
+    #[serde(skip)]
+    /// The recent frame times.
+    perf: Perf,
+    #[serde(skip)]
+    /// Whether the app was started in safe mode, in which nothing is saved.
+    safe_mode: bool,
+    #[serde(skip)]
+    /// The index of the focused entry in the log pane.
+    log_focus: Option<usize>,
+    #[serde(skip)]
+    /// The id of the log entry to scroll to & focus in the log pane.
+    log_jump: Option<usize>,
+    #[serde(skip)]
+    /// Corrupt page data found while starting, which is backed up once storage is writable.
+    corrupt: Vec<CorruptData>,
+    #[serde(skip)]
+    /// Whether storage is unavailable, so nothing will be saved.
+    ephemeral: bool,
+    #[serde(skip)]
+    /// Whether the browser is online, as fetches are skipped while offline.
+    online: bool,
+    #[serde(skip)]
+    /// Whether the user prefers reduced motion, so animations are disabled.
+    reduced_motion: bool,
+    #[serde(skip)]
+    /// Whether the page was switched, so the transition should start.
+    page_switched: bool,
+    #[serde(skip)]
+    /// The contents of the file picked to import state from.
+    import: Fetch<String>,
+    #[serde(skip)]
+    /// Whether state has been imported, so the app must reload without saving over it.
+    imported: bool,
+}
+
+impl Default for MyApp {
+    fn default() -> Self {
+        Self {
+            version: APP_VERSION,
+            page_data: PageData::Home,
+            dirty: false,
+            link_outdated: false,
+            stored_debug: None,
+            remember_debug: false,
+            palette: Palette::default(),
+            rendered: false,
+            dynamic_pages: Vec::new(),
+            dynamic_page: None,
+            zoom: DEFAULT_ZOOM,
+            windows: WindowState::default(),
+            console_input: String::new(),
+            layout: LayoutData::Desktop {},
+            auto_layout: true,
+            force_layout: None,
+            layout_pending: None,
+            idle: Idle::default(),
+            scroll_offsets: HashMap::new(),
+            restore_scroll: None,
+            home_url: None,
+            home_markdown: Fetch::default(),
+            home_doc: 0,
+            md_cache: Default::default(),
+            log_capacity: DEFAULT_LOG_CAPACITY,
+            logs: CircularQueue::with_capacity(DEFAULT_LOG_CAPACITY),
+            log_format: LogFormat::default(),
+            log_wrap: true,
+            log_compact: true,
+            log_autoscroll: true,
+            log_scroll_offset: 0.0,
+            log_seen_id: 0,
+            log_display_len: DEFAULT_LOG_DISPLAY_LEN,
+            display_filter: log::LevelFilter::Trace,
+            log_search: String::new(),
+            log_receiver: None,
+            logger_warning: false,
+            whats_new: false,
+            profile: Fetch::default(),
+            profile_requested: 0.0,
+            theme: None,
+            accent: None,
+            lang: None,
+            next_log_id: 0,
+            log_level: log::LevelFilter::Trace,
+            toasts: Vec::new(),
+            confirm_reload: false,
+            confirm_reset: false,
+            confirm_export: None,
+            export_format: ExportFormat::default(),
+            throttle: Throttle::default(),
+            perf: Perf::default(),
+            safe_mode: false,
+            log_focus: None,
+            log_jump: None,
+            corrupt: Vec::new(),
+            ephemeral: false,
+            online: true,
+            reduced_motion: false,
+            page_switched: false,
+            import: Fetch::default(),
+            imported: false,
+        }
+    }
+}
+
+impl MyApp {
+    /// Gets the [`Page`] that the current [`PageData`] represents.
+    pub fn page(&self) -> Page {
+        self.page_data.kind()
+    }
+
+    /// Gets the [`Layout`] to render, which is the forced layout if there is one.
+    ///
+    /// Otherwise it is the layout that the current [`LayoutData`] represents.
+    pub fn layout(&self) -> Layout {
+        self.force_layout.unwrap_or_else(|| self.layout.kind())
+    }
+
+    /// Saves the current [`PageData`] & loads the [`PageData`] for the given [`Page`].
+    ///
+    /// The page is switched even if saving fails.
+    pub fn switch_page(&mut self, page: Page, frame: &mut dyn Host) -> Result<(), SaveError> {
+        log::debug!("Switching page: {} -> {}", self.page(), page);
+        self.leave_dynamic_page(frame);
+        let saved = self.save_page(frame);
+        self.page_data = self.load_page(page, frame);
+        self.dirty = false;
+        self.page_switched = true;
+        js_imports::set_hash(&self.page_hash());
+        // Returns to where the page was scrolled to when it was last open.
+        self.restore_scroll = Some(self.scroll_offsets.get(&page).copied().unwrap_or(0.0));
+        saved
+    }
+
+    /// Saves the current [`PageData`], unless in safe mode where nothing is saved.
+    fn save_page(&self, frame: &mut dyn Host) -> Result<(), SaveError> {
+        if self.safe_mode {
+            return Ok(());
+        }
+        self.page_data.save(frame)
+    }
+
+    /// Loads the [`PageData`] for the given [`Page`].
+    ///
+    /// Safe mode uses the defaults instead, as the stored data may be what it is recovering from.
+    fn load_page(&self, page: Page, frame: &mut dyn Host) -> PageData {
+        match self.safe_mode {
+            true => page.into(),
+            false => page.load(frame),
+        }
+    }
+
+    /// Gets the url fragment linking to the current page.
+    ///
+    /// The example page also links to its label & value, so it can be shared preconfigured.
+    fn page_hash(&self) -> String {
+        match &self.page_data {
+            PageData::Example(example) => {
+                format!("{}?{}", self.page().fragment(), example.link_query())
+            }
+            _ => self.page().fragment(),
+        }
+    }
+
+    /// Sets the scale of the UI, clamped to [`ZOOM_RANGE`].
+    fn set_zoom(&mut self, ctx: &egui::Context, zoom: f32) {
+        self.zoom = zoom.clamp(*ZOOM_RANGE.start(), *ZOOM_RANGE.end());
+        ctx.set_pixels_per_point(self.zoom);
+    }
+
+    /// Adds a page to the navigation, after the built in pages.
+    ///
+    /// The page's data is stored under a key derived from its name, so the name should be unique.
+    pub fn register_page(&mut self, name: impl Into<String>, view: Box<dyn PageView>) {
+        let name = name.into();
+        let taken = Page::all().iter().any(|page| page.to_string() == name)
+            || self.dynamic_pages.iter().any(|page| page.name == name);
+        if taken {
+            log::warn!("A page called \"{name}\" already exists, so it wasn't registered.");
+            return;
+        }
+
+        log::debug!("Registered page: {name}");
+        self.dynamic_pages.push(DynamicPage { name, view });
+    }
+
+    /// Saves the current [`PageData`] & displays the registered page at the index.
+    fn switch_dynamic_page(&mut self, index: usize, frame: &mut dyn Host) -> Result<(), SaveError> {
+        self.leave_dynamic_page(frame);
+        let saved = self.save_page(frame);
+        self.dirty = false;
+
+        let Some(page) = self.dynamic_pages.get_mut(index) else {
+            log::error!("No registered page at index {index}.");
+            return saved;
+        };
+        log::debug!("Switching page: {} -> {}", self.page_data.kind(), page.name);
+
+        // Safe mode displays the page's defaults, in case its stored data is the problem.
+        let stored = frame
+            .storage()
+            .filter(|_| !self.safe_mode)
+            .and_then(|s| s.get_string(&page.storage_key()));
+        if let Some(data) = stored {
+            page.view.restore(&data);
+        }
+        self.dynamic_page = Some(index);
+        self.page_switched = true;
+        saved
+    }
+
+    /// Stores the registered page being displayed, if any, so a built in page can be displayed.
+    fn leave_dynamic_page(&mut self, frame: &mut dyn Host) {
+        if let (false, Some(storage)) = (self.safe_mode, frame.storage_mut()) {
+            self.store_dynamic_page(storage);
+        }
+        self.dynamic_page = None;
+    }
+
+    /// Stores the data of the registered page being displayed, if any.
+    fn store_dynamic_page(&self, storage: &mut dyn eframe::Storage) {
+        let Some(page) = self
+            .dynamic_page
+            .and_then(|index| self.dynamic_pages.get(index))
+        else {
+            return;
+        };
+        if let Some(data) = page.view.store() {
+            storage.set_string(&page.storage_key(), data);
+        }
+    }
+
+    /// Shows a toast if saving failed.
+    fn report_save(&mut self, result: Result<(), SaveError>) {
+        // The app already shows that nothing is saved without storage.
+        if let (Err(e), false) = (result, self.ephemeral) {
+            self.push_toast(log::Level::Error, e.to_string());
+        }
+    }
+
+    /// Shows a notification, for feedback on an action.
+    ///
+    /// Errors stay until dismissed, other levels fade away.
+    pub fn push_toast(&mut self, level: log::Level, message: impl Into<String>) {
+        toast::push(&mut self.toasts, Toast::new(level, message.into(), None));
+    }
+
+    /// Shares a link to the current page, or copies it if sharing is unsupported.
+    fn share_page(&mut self) {
+        let url = js_imports::href();
+
+        if !js_imports::can_share() {
+            log::debug!("Sharing is unsupported, copying the link instead.");
+            fetch::spawn_local(async move {
+                if let Err(e) = js_imports::copy_to_clipboard(&url).await {
+                    log::warn!("Unable to copy the link, the clipboard is unavailable: {e:?}");
+                }
+            });
+            self.push_toast(log::Level::Info, "Copied the link to this page.");
+            return;
+        }
+
+        let title = self.page().to_string();
+        fetch::spawn_local(async move {
+            // Also fails when the user closes the share sheet.
+            if let Err(e) = js_imports::share(&title, &url).await {
+                log::debug!("Page wasn't shared: {e:?}");
+            }
+        });
+    }
+
+    /// Displays a button for each page in the navigation, followed by the debug menu toggle.
+    fn page_buttons(&mut self, ui: &mut egui::Ui, frame: &mut dyn Host) {
+        for (page, _) in Page::nav() {
+            let label = format!("{} {}", page.icon(), page_label(page));
+            let selected = self.dynamic_page.is_none() && self.page() == page;
+            let unsaved = self.dirty && selected;
+            let label = match unsaved {
+                true => format!("{label} •"),
+                false => label,
+            };
+            let mut button = ui.add(egui::Button::new(label).selected(selected));
+            if unsaved {
+                button = button.on_hover_text("This page has unsaved changes.");
+            }
+            if button.clicked() {
+                let saved = self.switch_page(page, frame);
+                self.report_save(saved);
+            }
+        }
+
+        for index in 0..self.dynamic_pages.len() {
+            let name = self.dynamic_pages[index].name.clone();
+            let selected = self.dynamic_page == Some(index);
+            if ui.add(egui::Button::new(name).selected(selected)).clicked() {
+                let saved = self.switch_dynamic_page(index, frame);
+                self.report_save(saved);
+            }
+        }
+
+        ui.separator();
+
+        let debug_menu =
+            ui.add(egui::Button::new(tr("nav.debug_menu")).selected(self.windows.debug));
+        if debug_menu.clicked() {
+            self.windows.debug = !self.windows.debug;
+        }
+    }
+    /// Runs a command entered into the console.
+    fn run_command(&mut self, input: &str, frame: &mut dyn Host) {
+        log::info!("> {input}");
+
+        match Command::parse(input) {
+            Ok(Command::Help) => {
+                for (usage, description) in console::COMMANDS {
+                    log::info!("{usage}: {description}");
+                }
+            }
+            Ok(Command::Clear) => self.logs.clear(),
+            Ok(Command::Debug) => self.windows.debug = !self.windows.debug,
+            Ok(Command::Page(page)) => {
+                let saved = self.switch_page(page, frame);
+                self.report_save(saved);
+            }
+            Err(e) => log::warn!("{e}"),
+        }
+    }
+
+    /// Runs an action chosen from the command palette.
+    fn run_action(&mut self, action: Action, frame: &mut dyn Host) {
+        log::debug!("Running action: {action:?}");
+
+        match action {
+            Action::SwitchPage(page) => {
+                let saved = self.switch_page(page, frame);
+                self.report_save(saved);
+            }
+            Action::ToggleDebug => self.windows.debug = !self.windows.debug,
+            Action::ToggleLayout => self.toggle_layout(frame),
+            Action::ClearLogs => self.logs.clear(),
+            Action::ExportState => self.download_state(frame),
+        }
+    }
+
+    /// Switches to the next layout, which stops the layout following the window width.
+    fn toggle_layout(&mut self, frame: &mut dyn Host) {
+        // A manually chosen layout shouldn't be switched back.
+        self.auto_layout = false;
+        self.set_layout(match self.layout.kind() {
+            Layout::Desktop => LayoutData::Tablet {},
+            Layout::Tablet => LayoutData::Mobile {},
+            Layout::Mobile => LayoutData::Desktop {},
+        });
+        self.save_layout(frame);
+        log::info!("New Layout: {}", self.layout.kind());
+    }
+
+    /// Downloads the exported app state as a file.
+    fn download_state(&mut self, frame: &dyn Host) {
+        let format = self.export_format;
+        match self.export_state(frame.storage(), format) {
+            Ok(state) if state.len() > EXPORT_WARN_SIZE => {
+                let message = format!("The exported state is {}.", format_size(state.len()));
+                log::warn!("{message}");
+                self.push_toast(log::Level::Warn, message);
+                self.confirm_export = Some((format, state));
+            }
+            Ok(state) => {
+                js_imports::download(format.file_name(), &state);
+                self.push_toast(log::Level::Info, "Exported app state.");
+            }
+            Err(e) => log::error!("Failed to export app state: {e}"),
+        }
+    }
+
+    /// Displays the console overlay, with the recent logs & a command input.
+    fn console_ui(&mut self, ctx: &egui::Context, frame: &mut dyn Host) {
+        egui::Window::new("Console")
+            .title_bar(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 0.0])
+            .default_width(ctx.screen_rect().width())
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        // Oldest first, so the newest logs are by the input.
+                        for log in self.logs.asc_iter() {
+                            ui.monospace(log.format(LogFormat::WithTimestamp));
+                        }
+                    });
+
+                let input = ui.add(
+                    egui::TextEdit::singleline(&mut self.console_input)
+                        .hint_text("Enter a command, or \"help\".")
+                        .desired_width(f32::INFINITY)
+                        .code_editor(),
+                );
+
+                if input.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    let command = std::mem::take(&mut self.console_input);
+                    self.run_command(command.trim(), frame);
+                    input.request_focus();
+                }
+            });
+    }
+
+    /// Resizes the log buffer, keeping the most recent logs.
+    pub fn set_log_capacity(&mut self, capacity: usize) {
+        // The buffer can't be empty, & imported state mustn't allocate a huge buffer.
+        let capacity = capacity.clamp(*LOG_CAPACITY_RANGE.start(), *LOG_CAPACITY_RANGE.end());
+        log::debug!("Log capacity changed: {} -> {capacity}", self.log_capacity);
+
+        let mut logs = CircularQueue::with_capacity(capacity);
+        for log in self.logs.asc_iter() {
+            logs.push(log.clone());
+        }
+
+        self.logs = logs;
+        self.log_capacity = capacity;
+    }
+
+    /// Gets the logs in the log buffer, oldest first.
+    ///
+    /// Logs are only added to the buffer by [`MyApp::drain_logs`], which runs every frame.
+    pub fn logs(&self) -> impl Iterator<Item = &LogType> {
+        self.logs.asc_iter()
+    }
+
+    /// Receives the pending logs from the logger, up to [`MAX_LOGS_PER_FRAME`].
+    ///
+    /// Returns whether any logs were received.
+    pub fn drain_logs(&mut self) -> bool {
+        let Some(receiver) = &self.log_receiver else {
+            return false;
+        };
+
+        logger::flush_expired_repeats();
+
+        // Any logs past the limit are received on later frames, so a flood can't stall the ui.
+        let logs: Vec<LogType> = receiver.try_iter().take(MAX_LOGS_PER_FRAME).collect();
+
+        let logged = !logs.is_empty();
+        for entry in logs {
+            self.handle_log(entry);
+        }
+        logged
+    }
+
+    /// Scrolls the log pane to the log entry with the given id, & focuses it.
+    ///
+    /// The search is cleared so the entry is shown.
+    pub fn focus_log(&mut self, log_id: usize) {
+        self.log_jump = Some(log_id);
+        self.log_search.clear();
+        self.log_autoscroll = false;
+    }
+
+    /// Adds a received log to the log buffer.
+    ///
+    /// Logs more verbose than the logged levels are dropped, as they were sent before it changed.
+    /// A log identical to the newest buffered log is merged into it, counting the repeat.
+    pub fn handle_log(&mut self, entry: LogType) {
+        if entry.level > self.log_level {
+            return;
+        }
+
+        // Errors are shown as toasts so they aren't missed.
+        let toast = (entry.level == log::Level::Error).then(|| entry.message.clone());
+
+        let newest = self.logs.iter_mut().next().filter(|newest| {
+            newest.level == entry.level
+                && newest.target == entry.target
+                && newest.message == entry.message
+        });
+        match newest {
+            Some(newest) => {
+                newest.count += entry.count.max(1);
+                newest.time = entry.time;
+            }
+            None => {
+                self.next_log_id += 1;
+                self.logs.push(entry);
+            }
+        }
+
+        if let Some(message) = toast {
+            let log_id = Some(self.next_log_id - 1);
+            toast::push(
+                &mut self.toasts,
+                Toast::new(log::Level::Error, message, log_id),
+            );
+        }
+    }
+
+    /// Displays the options for fetching the home page from a url.
+    fn home_url_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let mut url = self.home_url.clone().unwrap_or_default();
+            ui.add(egui::TextEdit::singleline(&mut url).hint_text("Embedded page"));
+
+            let url = url.trim();
+            self.home_url = (!url.is_empty()).then(|| url.to_owned());
+
+            if let Some(url) = &self.home_url {
+                if ui.button("Fetch").clicked() {
+                    home::spawn_fetch(url.clone(), self.home_markdown.sender(), ui.ctx().clone());
+                }
+            }
+        });
+    }
+
+    /// Displays the log entries, newest first.
+    ///
+    /// While an entry has keyboard focus, the arrow keys & Home/End move between entries,
+    /// and Enter/C copies the focused entry.
+    fn log_pane(&mut self, ui: &mut egui::Ui) {
+        if self.log_receiver.is_none() {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                "Logging is unavailable, see the browser console instead.",
+            );
+            return;
+        }
+
+        // The logs are newest first, so the newest has the last id.
+        let newest_id = self.next_log_id.wrapping_sub(1);
+        let search = self.log_search.to_lowercase();
+        let entries: Vec<(usize, log::Level, String)> = self
+            .logs
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| log_shown(entry, self.display_filter, &search))
+            .map(|(age, entry)| (newest_id - age, entry.level, entry.format(self.log_format)))
+            .map(|(id, level, entry)| match self.log_compact {
+                true => (id, level, compact_log(&entry)),
+                false => (id, level, entry),
+            })
+            .collect();
+
+        // Entries that were evicted or filtered out can't be jumped to.
+        if let Some(log_id) = self.log_jump {
+            if !entries.iter().any(|(id, ..)| *id == log_id) {
+                log::debug!("Log {log_id} isn't shown, so it can't be focused.");
+                self.log_jump = None;
+            }
+        }
+        let mut responses = Vec::with_capacity(entries.len());
+        let mut focused = None;
+
+        // The logs received since the last frame, which are shown above the others.
+        let fresh = self.next_log_id - std::mem::replace(&mut self.log_seen_id, self.next_log_id);
+        let fresh_shown = self
+            .logs
+            .iter()
+            .take(fresh)
+            .filter(|entry| log_shown(entry, self.display_filter, &search))
+            .count();
+
+        let mut scroll_area = egui::ScrollArea::vertical()
+            .hscroll(!self.log_wrap)
+            .max_height(300.0)
+            .auto_shrink([false, true]);
+        // The newest logs are first, so following them means staying at the top.
+        if self.log_autoscroll {
+            scroll_area = scroll_area.vertical_scroll_offset(0.0);
+        }
+
+        let output = scroll_area.show(ui, |ui| {
+            ui.style_mut().wrap_mode = Some(match self.log_wrap {
+                true => egui::TextWrapMode::Wrap,
+                false => egui::TextWrapMode::Extend,
+            });
+
+            for (index, (id, level, entry)) in entries.iter().enumerate() {
+                let colour = logger::level_colour(ui.visuals(), *level);
+                let selected = self.log_focus == Some(index);
+                let response = match shorten_log(entry, self.log_display_len) {
+                    Some(short) => ui
+                        .selectable_label(selected, egui::RichText::new(short).color(colour))
+                        .on_hover_text(entry),
+                    None => ui.selectable_label(selected, egui::RichText::new(entry).color(colour)),
+                };
+                if self.log_jump == Some(*id) {
+                    self.log_jump = None;
+                    response.request_focus();
+                    response.scroll_to_me(Some(egui::Align::Center));
+                }
+                if response.clicked() || response.has_focus() {
+                    self.log_focus = Some(index);
+                }
+                if response.has_focus() {
+                    focused = Some(index);
+                }
+                // The entries are read-only, but can still be copied with the mouse.
+                response.context_menu(|ui| {
+                    if ui.button("Copy").clicked() {
+                        ui.ctx().copy_text(entry.to_owned());
+                        ui.close_menu();
+                    }
+                });
+                responses.push(response);
+            }
+
+            // Moves down by the height of the new logs, so the logs being read stay still.
+            if !self.log_autoscroll && fresh_shown > 0 {
+                if let (Some(first), Some(kept)) = (responses.first(), responses.get(fresh_shown)) {
+                    let added = kept.rect.top() - first.rect.top();
+                    ui.scroll_with_delta(egui::vec2(0.0, -added));
+                }
+            }
+        });
+
+        // Scrolling away from the newest logs stops following them, & scrolling back resumes it.
+        let offset = output.state.offset.y;
+        if self.log_autoscroll && offset > 1.0 {
+            self.log_autoscroll = false;
+        } else if !self.log_autoscroll && offset <= 1.0 && self.log_scroll_offset > 1.0 {
+            self.log_autoscroll = true;
+        }
+        self.log_scroll_offset = offset;
+
+        // Keys are only handled while the pane has focus, so global keys aren't hijacked.
+        let Some(index) = focused else {
+            return;
+        };
+
+        // Stops egui from also moving focus with the arrow keys.
+        let filter = egui::EventFilter {
+            vertical_arrows: true,
+            ..Default::default()
+        };
+        ui.memory_mut(|m| m.set_focus_lock_filter(responses[index].id, filter));
+
+        let (up, down, home, end, copy) = ui.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Home),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::End),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter)
+                    | i.consume_key(egui::Modifiers::NONE, egui::Key::C),
+            )
+        });
+
+        let last = entries.len() - 1;
+        let target = match (up, down, home, end) {
+            (true, ..) => index.saturating_sub(1),
+            (_, true, ..) => (index + 1).min(last),
+            (_, _, true, _) => 0,
+            (.., true) => last,
+            _ => index,
+        };
+
+        if target != index {
+            responses[target].request_focus();
+            responses[target].scroll_to_me(None);
+            self.log_focus = Some(target);
+        }
+
+        if copy {
+            ui.ctx().copy_text(entries[index].2.trim_end().to_owned());
+        }
+    }
+
+    /// Switches the layout when the window width crosses a layout breakpoint.
+    ///
+    /// The width has to stay past the breakpoint for a moment, so resizing doesn't thrash the layout.
+    fn auto_layout(&mut self, ctx: &egui::Context, frame: &mut dyn Host) {
+        if !self.auto_layout {
+            return;
+        }
+
+        let target = Layout::for_width(js_imports::inner_width());
+
+        if target == self.layout.kind() {
+            self.layout_pending = None;
+            return;
+        }
+
+        let now = ctx.input(|i| i.time);
+        match self.layout_pending {
+            Some((pending, since)) if pending == target && now - since >= LAYOUT_DEBOUNCE => {
+                self.layout_pending = None;
+                self.set_layout(target.into());
+                self.save_layout(frame);
+            }
+            Some((pending, _)) if pending == target => {}
+            _ => {
+                self.layout_pending = Some((target, now));
+                ctx.request_repaint_after(Duration::from_secs_f64(LAYOUT_DEBOUNCE));
+            }
+        }
+    }
+
+    /// Sets the [`LayoutData`] to render.
+    pub fn set_layout(&mut self, layout: LayoutData) {
+        log::debug!(
+            "Layout changed: {} -> {}",
+            self.layout.kind(),
+            layout.kind()
+        );
+        self.layout = layout;
+    }
+
+    /// Immediately writes the layout to storage, so it isn't lost if the tab is closed.
+    pub fn save_layout(&self, frame: &mut dyn Host) {
+        if self.safe_mode {
+            return;
+        }
+        if let Some(storage) = frame.storage_mut() {
+            eframe::set_value(storage, LAYOUT_KEY, &self.layout);
+        }
+    }
+
+    /// Immediately writes all app data to storage.
+    ///
+    /// Returns false if storage is unavailable.
+    pub fn flush_save(&mut self, frame: &mut dyn Host) -> bool {
+        let saved = self.save_page(frame);
+        self.dirty &= saved.is_err();
+        self.report_save(saved);
+
+        match frame.storage_mut() {
+            Some(storage) => {
+                eframe::App::save(self, storage);
+                storage.flush();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Checks whether the browser is online, retrying failed fetches once it reconnects.
+    fn update_online(&mut self, ctx: &egui::Context) {
+        let online = js_imports::is_online();
+        if online == self.online {
+            return;
+        }
+
+        self.online = online;
+        match online {
+            true => log::info!("Back online."),
+            false => {
+                log::warn!("Offline, remote content won't be fetched.");
+                return;
+            }
+        }
+
+        if let FetchState::Failed(_) = self.profile.state() {
+            profile::spawn_fetch(self.profile.sender(), ctx.clone());
+            self.profile_requested = ctx.input(|i| i.time);
+        }
+        if let (Some(url), false) = (
+            &self.home_url,
+            matches!(self.home_markdown.state(), FetchState::Done(_)),
+        ) {
+            home::spawn_fetch(url.clone(), self.home_markdown.sender(), ctx.clone());
+        }
+    }
+
+    /// Gets a summary of the app's state.
+    pub fn snapshot(&self) -> AppSnapshot {
+        AppSnapshot {
+            page: self.page(),
+            layout: self.layout(),
+            debug_window: self.windows.debug,
+            log_count: self.logs.len(),
+            receiving_logs: self.log_receiver.is_some(),
+        }
+    }
+
+    /// Gets the url of a new issue, with the body prefilled with the environment & recent errors.
+    pub fn issue_url(&self) -> String {
+        let mut errors: Vec<String> = self
+            .logs
+            .iter()
+            .filter(|entry| entry.level == log::Level::Error)
+            .take(ISSUE_ERRORS)
+            .map(|entry| format!("- {}", entry.format(LogFormat::WithTimestamp)))
+            .collect();
+        // Oldest first, as they happened.
+        errors.reverse();
+        if errors.is_empty() {
+            errors.push("None".to_owned());
+        }
+
+        let body = format!(
+            "**Describe the issue:**\n\n\n\
+            **Environment:**\n\
+            - Version: {} ({})\n\
+            - User agent: {}\n\
+            - Platform: {}\n\
+            - Layout: {}\n\
+            - Page: {}\n\n\
+            **Recent errors:**\n{}\n",
+            MyApp::version(),
+            MyApp::commit().unwrap_or("unknown commit"),
+            js_imports::user_agent(),
+            js_imports::platform(),
+            self.layout(),
+            self.page(),
+            errors.join("\n"),
+        );
+
+        format!("{ISSUE_URL}?body={}", url_encode(&body))
+    }
+
+    /// Loads the stored data for every page, without modifying storage.
+    ///
+    /// Pages without stored data have their default data.
+    pub fn all_page_data(&self, storage: Option<&dyn eframe::Storage>) -> Vec<(Page, PageData)> {
+        Page::all()
+            .iter()
+            .copied()
+            .filter(|page| *page != Page::NotFound)
+            .map(|page| (page, page.load_from(storage)))
+            .collect()
+    }
+
+    /// Serializes the app state & the data for every page, keyed by their storage keys.
+    pub fn export_state(
+        &self,
+        storage: Option<&dyn eframe::Storage>,
+        format: ExportFormat,
+    ) -> Result<String, String> {
+        let state = self.export_values(storage).map_err(|e| e.to_string())?;
+
+        match format {
+            ExportFormat::Json => serde_json::to_string_pretty(&state).map_err(|e| e.to_string()),
+            ExportFormat::Ron => {
+                ron::ser::to_string_pretty(&state, ron::ser::PrettyConfig::default())
+                    .map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    /// Collects the app state & the data of every page, keyed by their storage keys.
+    fn export_values(
+        &self,
+        storage: Option<&dyn eframe::Storage>,
+    ) -> serde_json::Result<serde_json::Map<String, serde_json::Value>> {
+        let mut state = serde_json::Map::new();
+        state.insert(STORAGE_KEY.to_owned(), serde_json::to_value(self)?);
+
+        if let Some(theme) = self.theme {
+            state.insert(THEME_KEY.to_owned(), serde_json::to_value(theme)?);
+        }
+        // Read from its own key at startup, so it isn't restored from the app state.
+        state.insert(ACCENT_KEY.to_owned(), serde_json::to_value(self.accent)?);
+
+        for (page, stored) in self.all_page_data(storage) {
+            // The current page may have unsaved changes.
+            let page_data = match page == self.page() {
+                true => &self.page_data,
+                false => &stored,
+            };
+
+            let versioned = Versioned {
+                version: page_data.version(),
+                data: page_data,
+            };
+            state.insert(
+                page_storage_key!(page).to_owned(),
+                serde_json::to_value(versioned)?,
+            );
+        }
+
+        Ok(state)
+    }
+
+    /// Writes state exported by [`MyApp::export_state`] into storage.
+    ///
+    /// Nothing is written unless the whole state is valid.
+    pub fn import_state(
+        &mut self,
+        state: &str,
+        storage: Option<&mut (dyn eframe::Storage + 'static)>,
+    ) -> Result<(), String> {
+        // The format is detected, so either can be imported.
+        let state: serde_json::Map<String, serde_json::Value> = match serde_json::from_str(state) {
+            Ok(state) => state,
+            Err(json_error) => ron::from_str(state).map_err(|ron_error| {
+                format!("Invalid json ({json_error}) or ron ({ron_error}).")
+            })?,
+        };
+
+        let app = state
+            .get(STORAGE_KEY)
+            .cloned()
+            .and_then(MyApp::migrate)
+            .ok_or("Missing or invalid app state.")?;
+
+        let theme: Option<egui::ThemePreference> = match state.get(THEME_KEY) {
+            Some(theme) => Some(
+                serde_json::from_value(theme.clone()).map_err(|e| format!("Invalid theme: {e}"))?,
+            ),
+            None => None,
+        };
+
+        let accent: Option<Option<egui::Color32>> = match state.get(ACCENT_KEY) {
+            Some(accent) => Some(
+                serde_json::from_value(accent.clone())
+                    .map_err(|e| format!("Invalid accent: {e}"))?,
+            ),
+            None => None,
+        };
+
+        let mut pages = Vec::new();
+        for page in Page::all().iter().copied() {
+            let Some(page_data) = state.get(page_storage_key!(page)) else {
+                continue;
+            };
+
+            let Versioned { version, data }: Versioned<PageData> =
+                serde_json::from_value(page_data.clone())
+                    .map_err(|e| format!("Invalid {page} page data: {e}"))?;
+            if data.kind() != page {
+                return Err(format!("The {page} page has {} page data.", data.kind()));
+            }
+            pages.push(data.migrate(version));
+        }
+
+        let storage = storage.ok_or("Storage is unavailable.")?;
+        let app = serde_json::to_string(&app).map_err(|e| e.to_string())?;
+        storage.set_string(STORAGE_KEY, app);
+        if let Some(theme) = theme {
+            eframe::set_value(storage, THEME_KEY, &theme);
+        }
+        if let Some(accent) = accent {
+            eframe::set_value(storage, ACCENT_KEY, &accent);
+        }
+
+        for page_data in pages {
+            page_data
+                .save_to(Some(&mut *storage))
+                .map_err(|e| e.to_string())?;
+        }
+
+        self.imported = true;
+        Ok(())
+    }
+}
+
+impl MyApp {
+    /// Upgrades app state stored with an older version.
+    ///
+    /// Returns `None` if the state is invalid.
+    pub fn migrate(mut raw: serde_json::Value) -> Option<MyApp> {
+        let version = raw
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0);
+        let state = raw.as_object_mut()?;
+
+        if version < 1 {
+            // Version 0 may predate the layout, which is filled in with the default.
+            if !state.contains_key("layout") {
+                let layout = serde_json::to_value(LayoutData::default()).ok()?;
+                state.insert("layout".to_owned(), layout);
+            }
+        }
+
+        if version < 2 {
+            // Version 1 stored whether each window is open separately.
+            let mut windows = serde_json::Map::new();
+            for (old, new) in [
+                ("debug_window", "debug"),
+                ("console", "console"),
+                ("show_perf", "perf"),
+            ] {
+                if let Some(open) = state.remove(old) {
+                    windows.insert(new.to_owned(), open);
+                }
+            }
+            state.insert("windows".to_owned(), windows.into());
+        }
+
+        match serde_json::from_value::<MyApp>(raw) {
+            Ok(mut app) => {
+                if version < APP_VERSION as u64 {
+                    log::debug!("Migrated app state from version {version}.");
+                }
+                app.version = APP_VERSION;
+                Some(app)
+            }
+            Err(e) => {
+                log::warn!("Unable to read stored app state: {e}");
+                None
+            }
+        }
+    }
+}
+
+/// The fields of app state stored before versioning, which have since been moved.
+#[derive(serde::Deserialize, Default)]
+#[serde(default)]
+struct LegacyWindows {
+    debug_window: bool,
+    console: bool,
+    show_perf: bool,
+}
+
+impl MyApp {
+    /// Upgrades app state stored as ron, which eframe stored before versioning.
+    ///
+    /// Returns `None` if the state is invalid.
+    pub fn migrate_ron(raw: &str) -> Option<MyApp> {
+        // Read as typed data, as ron can't describe enums without knowing their type.
+        let app: MyApp = ron::from_str(raw)
+            .map_err(|e| log::warn!("Unable to read stored app state: {e}"))
+            .ok()?;
+        let legacy: LegacyWindows = ron::from_str(raw)
+            .map_err(|e| log::warn!("Unable to read the windows in stored app state: {e}"))
+            .ok()?;
+
+        let mut state = serde_json::to_value(app).ok()?;
+        let object = state.as_object_mut()?;
+        object.insert("version".to_owned(), 0.into());
+        // Recreated from the legacy fields by the migration.
+        object.remove("windows");
+        for (key, open) in [
+            ("debug_window", legacy.debug_window),
+            ("console", legacy.console),
+            ("show_perf", legacy.show_perf),
+        ] {
+            object.insert(key.to_owned(), open.into());
+        }
+
+        MyApp::migrate(state)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum InitError {
+    #[error("Unable to access storage.")]
+    StorageError(),
+}
+
+impl MyApp {
+    /// The version of the running build.
+    pub fn version() -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    /// The commit the running build was built from, if it was built from a git checkout.
+    pub fn commit() -> Option<&'static str> {
+        option_env!("TYE_HOME_GIT_HASH")
+    }
+
+    /// Creates an app that isn't backed by storage, so nothing is loaded or saved.
+    ///
+    /// Unlike [`MyApp::new`], this doesn't need eframe or the browser, so the app can be driven headless.
+    pub fn new_in_memory() -> Self {
+        MyApp {
+            ephemeral: true,
+            ..Default::default()
+        }
+    }
+
+    /// Displays the logs received from the logger in the log pane.
+    ///
+    /// Without a receiver the logger failed to install, so a warning is shown instead.
+    pub fn with_log_receiver(mut self, log_receiver: Option<mpsc::Receiver<LogType>>) -> Self {
+        self.logger_warning = log_receiver.is_none();
+        self.log_receiver = log_receiver;
+        self
+    }
+
+    /// Called once before the first frame.
+    pub fn new(
+        cc: &eframe::CreationContext<'_>,
+        log_receiver: Option<mpsc::Receiver<LogType>>,
+    ) -> Result<Self, InitError> {
+        // This is also where you can customize the look and feel of egui using
+        // `cc.egui_ctx.set_visuals` and `cc.egui_ctx.set_fonts`.
+
+        // Safe mode skips anything that could fail, so storage can be inspected & reset.
+        if js_imports::query_param("safe").as_deref() == Some("1") {
+            log::warn!("Starting in safe mode.");
+            cc.egui_ctx.set_pixels_per_point(DEFAULT_ZOOM);
+
+            let app = MyApp {
+                windows: WindowState {
+                    debug: true,
+                    ..Default::default()
+                },
+                safe_mode: true,
+                ..Default::default()
+            };
+            // Nothing is fetched, so the profile would otherwise be loading forever.
+            app.profile
+                .force(FetchState::Failed("Disabled in safe mode.".to_owned()));
+            return Ok(app.with_log_receiver(log_receiver));
+        }
+
+        let reduced_motion = js_imports::prefers_reduced_motion();
+        cc.egui_ctx
+            .style_mut(|style| apply_motion_preference(style, reduced_motion));
+
+        // Required to display images from urls, such as the Discord avatar.
+        egui_extras::install_image_loaders(&cc.egui_ctx);
+        // Logs from background tasks wake the app, even while it is throttled.
+        logger::repaint_on_log(&cc.egui_ctx);
+
+        // let response = reqwest::blocking::
+        // log::debug!()
+
+        // Load previous app state (if any).
+        // let mut app: MyApp = cc
+        //     .storage
+        //     .and_then(|storage| eframe::get_value::<_>(storage, STORAGE_KEY))
+        //     .map(|mut app: MyApp| {app.log_receiver = log_receiver; app})
+        //     .unwrap_or_default();
+
+        // app.layout = match js_imports::is_mobile() {
+        //     true => LayoutData::Mobile { tabs_open:  },
+        //     false => LayoutData::Desktop {  },
+        // }
+
+        // app
+
+        // Storage can be disabled, such as in some private browsing modes.
+        let storage = cc.storage;
+        if storage.is_none() {
+            log::warn!("{} Running without saving.", InitError::StorageError());
+        }
+
+        let stored = storage.and_then(|storage| match storage.get_string(STORAGE_KEY) {
+            Some(raw) => match serde_json::from_str(&raw) {
+                Ok(raw) => MyApp::migrate(raw),
+                // State saved before versioning is stored as ron.
+                Err(_) => MyApp::migrate_ron(&raw),
+            },
+            None => None,
+        });
+
+        let app = stored.unwrap_or_else(|| {
+            let layout = storage
+                .and_then(|storage| eframe::get_value(storage, LAYOUT_KEY))
+                .unwrap_or_else(|| match js_imports::is_mobile() {
+                    true => LayoutData::Mobile {},
+                    false => LayoutData::Desktop {},
+                });
+            MyApp {
+                layout,
+                ..Default::default()
+            }
+        });
+
+        let mut app = app.with_log_receiver(log_receiver);
+
+        // The missing page is only for the link that was followed, not later visits.
+        if app.page() == Page::NotFound {
+            app.page_data = PageData::Home;
+        }
+
+        // So the debug window doesn't surprise visitors sharing the storage.
+        if !app.remember_debug {
+            app.windows.debug = false;
+        }
+
+        // Opens the debug window for a debug link, without remembering it for later visits.
+        if js_imports::query_param("debug").as_deref() == Some("1") {
+            log::info!("Opening the debug window, as requested by the url.");
+            app.stored_debug = Some(app.windows.debug);
+            app.windows.debug = true;
+        }
+        // The zoom is managed by the app, so it can be stored.
+        cc.egui_ctx.options_mut(|o| o.zoom_with_keyboard = false);
+        app.set_zoom(&cc.egui_ctx, app.zoom);
+        app.reduced_motion = reduced_motion;
+        app.ephemeral = storage.is_none();
+        app.set_log_capacity(app.log_capacity);
+
+        // A chosen default layout overrides the stored layout.
+        let (settings, corrupt) = Page::Settings.load_checked(storage);
+        app.corrupt.extend(corrupt);
+        if let PageData::Settings(settings) = settings {
+            if let Some(layout) = settings.default_layout {
+                app.auto_layout = false;
+                app.layout = layout.into();
+            }
+        }
+
+        // Links to a page take priority over the stored page.
+        let hash = js_imports::get_hash();
+        let (fragment, query) = hash.split_once('?').unwrap_or((&hash, ""));
+        if !fragment.is_empty() {
+            match Page::from_fragment(fragment) {
+                Some(page) if page != app.page() => {
+                    let (page_data, corrupt) = page.load_checked(storage);
+                    app.page_data = page_data;
+                    app.corrupt.extend(corrupt);
+                }
+                Some(_) => {}
+                None => {
+                    app.page_data = PageData::NotFound {
+                        attempted: hash.clone(),
+                    }
+                }
+            }
+        }
+        if let (false, PageData::Example(example)) = (query.is_empty(), &mut app.page_data) {
+            log::info!("Configuring the example page, as requested by the url.");
+            example.apply_link_query(query);
+        }
+        app.restore_scroll = app.scroll_offsets.get(&app.page()).copied();
+
+        // The url to a missing page is kept, so it can be corrected.
+        if app.page() != Page::NotFound {
+            js_imports::set_hash(&app.page_hash());
+        }
+
+        app.online = js_imports::is_online();
+        match app.online {
+            true => profile::spawn_fetch(app.profile.sender(), cc.egui_ctx.clone()),
+            false => app.profile.force(FetchState::Failed("Offline.".to_owned())),
+        }
+
+        if let Some(url) = app.home_url.clone() {
+            match storage.and_then(|storage| home::load_cached(storage, &url)) {
+                Some(markdown) => app.home_markdown = Fetch::new(FetchState::Done(markdown)),
+                None if app.online => {
+                    home::spawn_fetch(url, app.home_markdown.sender(), cc.egui_ctx.clone())
+                }
+                // The embedded page is shown until back online.
+                None => {}
+            }
+        }
+
+        i18n::set_lang(app.lang.unwrap_or_else(Lang::browser));
+
+        // Without a stored theme, egui follows the system theme as it changes.
+        app.theme = storage.and_then(|storage| eframe::get_value(storage, THEME_KEY));
+        match app.theme {
+            Some(theme) => cc.egui_ctx.set_theme(theme),
+            None => {
+                // Used if eframe can't detect the system theme itself.
+                let fallback = match js_imports::prefers_dark() {
+                    true => egui::Theme::Dark,
+                    false => egui::Theme::Light,
+                };
+                log::debug!("Fallback theme: {fallback:?}");
+                cc.egui_ctx.options_mut(|o| o.fallback_theme = fallback);
+            }
+        }
+
+        // Stored as an option, so a removed accent isn't restored.
+        app.accent = storage
+            .and_then(|storage| eframe::get_value::<Option<egui::Color32>>(storage, ACCENT_KEY))
+            .flatten();
+        apply_accent(&cc.egui_ctx, app.accent);
+
+        // Fresh installs have no stored version, so are treated as up to date.
+        let last_version: Option<String> =
+            storage.and_then(|storage| eframe::get_value(storage, VERSION_KEY));
+        app.whats_new = last_version.is_some_and(|version| version != MyApp::version());
+
+        log::info!(
+            "User agent: {}, platform: {}",
+            js_imports::user_agent(),
+            js_imports::platform()
+        );
+
+        log::info!(
+            "Running version {} ({}).",
+            MyApp::version(),
+            MyApp::commit().unwrap_or("unknown commit")
+        );
+
+        log::debug!("App started.");
+        Ok(app)
+    }
+}
+
+impl eframe::App for MyApp {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        // Safe mode shouldn't overwrite the state it was started to recover.
+        if self.safe_mode {
+            return;
+        }
+        // Nor should the in-memory state overwrite imported state.
+        if self.imported {
+            return;
+        }
+
+        log::debug!("Saving app state.");
+        let debug = self.windows.debug;
+        if let Some(stored_debug) = self.stored_debug {
+            self.windows.debug = stored_debug;
+        }
+        // Stored as json, so older versions can be migrated.
+        match serde_json::to_string(self) {
+            Ok(state) => storage.set_string(STORAGE_KEY, state),
+            Err(e) => log::error!("Failed to save app state: {e}"),
+        }
+        self.windows.debug = debug;
+
+        if let Some(theme) = self.theme {
+            eframe::set_value(storage, THEME_KEY, &theme);
+        }
+        eframe::set_value(storage, ACCENT_KEY, &self.accent);
+        self.store_dynamic_page(storage);
+
+        // The version is only marked as seen once the changelog has been dismissed.
+        if !self.whats_new {
+            eframe::set_value(storage, VERSION_KEY, &MyApp::version());
+        }
+    }
+
+    /// Called each time the UI needs repainting, which may be many times per second.
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        self.show(ctx, frame);
+    }
+}
+
+impl MyApp {
+    /// Displays the app, storing its state in the host.
+    ///
+    /// This is [`eframe::App::update`] without eframe's frame, so the app can be driven headless.
+    pub fn show(&mut self, ctx: &egui::Context, frame: &mut dyn Host) {
+        // Put your widgets into a `SidePanel`, `TopBottomPanel`, `CentralPanel`, `Window` or `Area`.
+        // For inspiration and more examples, go to https://emilk.github.io/egui
+
+        // The storage given while starting is read-only, so corrupt data is backed up here.
+        if let Some(storage) = frame.storage_mut() {
+            for corrupt in self.corrupt.drain(..) {
+                corrupt.back_up(storage);
+            }
+        }
+
+        self.auto_layout(ctx, frame);
+
+        let mut fetched = self.profile.poll();
+
+        if self.home_markdown.poll() {
+            fetched = true;
+
+            let storage = frame.storage_mut().filter(|_| !self.safe_mode);
+            if let (FetchState::Done(markdown), Some(url), Some(storage)) =
+                (self.home_markdown.state(), &self.home_url, storage)
+            {
+                home::store_cached(storage, url, markdown);
+            }
+        }
+
+        self.update_online(ctx);
+
+        if self.import.poll() {
+            match self.import.state() {
+                FetchState::Done(state) => {
+                    let state = state.clone();
+                    match self.import_state(&state, frame.storage_mut()) {
+                        // Reloads so the imported state is used throughout.
+                        Ok(()) => js_imports::reload(),
+                        Err(e) => log::error!("Failed to import state: {e}"),
+                    }
+                }
+                FetchState::Failed(e) => log::error!("Unable to read the state file: {e}"),
+                FetchState::Loading => {}
+            }
+        }
+
+        // Ctrl + scroll zooms, like in the browser.
+        let zoom_delta = ctx.input(|i| i.zoom_delta());
+        if zoom_delta != 1.0 {
+            self.set_zoom(ctx, self.zoom * zoom_delta);
+        }
+
+        // Shortcuts don't fire while typing into a text field.
+        if !ctx.wants_keyboard_input() {
+            let toggle_console =
+                ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Backtick));
+            if toggle_console {
+                self.windows.console = !self.windows.console;
+            }
+
+            let toggle_debug =
+                ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::D));
+            if toggle_debug {
+                self.windows.debug = !self.windows.debug;
+            }
+
+            let toggle_palette =
+                ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::K));
+            if toggle_palette {
+                self.palette.toggle();
+            }
+
+            let (zoom_in, zoom_out, zoom_reset) = ctx.input_mut(|i| {
+                use egui::gui_zoom::kb_shortcuts::{ZOOM_IN, ZOOM_OUT, ZOOM_RESET};
+                (
+                    i.consume_shortcut(&ZOOM_IN),
+                    i.consume_shortcut(&ZOOM_OUT),
+                    i.consume_shortcut(&ZOOM_RESET),
+                )
+            });
+            match (zoom_in, zoom_out, zoom_reset) {
+                (true, ..) => self.set_zoom(ctx, self.zoom + ZOOM_STEP),
+                (_, true, _) => self.set_zoom(ctx, self.zoom - ZOOM_STEP),
+                (.., true) => self.set_zoom(ctx, DEFAULT_ZOOM),
+                _ => {}
+            }
+
+            // Alt + the page's position switches to it.
+            let page = ctx.input_mut(|i| {
+                Page::nav()
+                    .into_iter()
+                    .zip(PAGE_KEYS)
+                    .find(|(_, key)| i.consume_key(egui::Modifiers::ALT, *key))
+                    .map(|((page, _), _)| page)
+            });
+            if let Some(page) = page.filter(|page| *page != self.page()) {
+                let saved = self.switch_page(page, frame);
+                self.report_save(saved);
+            }
+        }
+
+        if self.idle.update(ctx) {
+            if self.page() != self.idle.page {
+                let saved = self.switch_page(self.idle.page, frame);
+                self.report_save(saved);
+            }
+            self.flush_save(frame);
+        }
+
+        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            // The top panel is often a good place for a menu bar:
+
+            egui::menu::bar(ui, |ui| {
+                let theme = ui.ctx().options(|o| o.theme_preference);
+                match self.layout() {
+                    Layout::Desktop => egui::widgets::global_theme_preference_buttons(ui),
+                    Layout::Tablet | Layout::Mobile => {
+                        egui::widgets::global_theme_preference_switch(ui)
+                    }
+                }
+
+                let new_theme = ui.ctx().options(|o| o.theme_preference);
+                if new_theme != theme {
+                    log::debug!("Theme changed: {theme:?} -> {new_theme:?}");
+                    self.theme = Some(new_theme);
+                }
+
+                ui.add(egui::Separator::default().vertical());
+
+                match self.layout() {
+                    Layout::Desktop => {
+                        self.page_buttons(ui, frame);
+                    }
+                    Layout::Tablet => {
+                        // The pages are in a dropdown, as there isn't room for them all.
+                        ui.menu_button(self.page().to_string(), |ui| {
+                            self.page_buttons(ui, frame);
+                        });
+                    }
+                    // The pages are in the bottom bar, to be in reach on phones.
+                    Layout::Mobile => {}
+                }
+
+                ui.menu_button("Window", |ui| self.windows.menu_ui(ui));
+
+                ui.add(egui::Separator::default().vertical());
+                if ui.button("−").on_hover_text("Zoom out").clicked() {
+                    self.set_zoom(ctx, self.zoom - ZOOM_STEP);
+                }
+                let zoom_label = format!("{:.0}%", self.zoom * 100.0);
+                if ui.button(zoom_label).on_hover_text("Reset zoom").clicked() {
+                    self.set_zoom(ctx, DEFAULT_ZOOM);
+                }
+                if ui.button("+").on_hover_text("Zoom in").clicked() {
+                    self.set_zoom(ctx, self.zoom + ZOOM_STEP);
+                }
+            });
+        });
+
+        if self.layout() == Layout::Mobile {
+            egui::TopBottomPanel::bottom("mobile_nav").show(ctx, |ui| {
+                // Large enough to tap easily.
+                ui.spacing_mut().interact_size.y = MOBILE_TAP_SIZE;
+                ui.horizontal_wrapped(|ui| {
+                    self.page_buttons(ui, frame);
+                    if ui.button("Share").clicked() {
+                        self.share_page();
+                    }
+                });
+            });
+        }
+
+        if self.windows.debug {
+            egui::Window::new("Debug window").show(ctx, |ui| {
+                ui.collapsing("Debug Page", |ui| {
+                    match serde_json::to_string_pretty(&self.snapshot()) {
+                        Ok(snapshot) => ui.monospace(snapshot),
+                        Err(e) => ui.label(format!("Unable to serialize the app state: {e}")),
+                    };
+                });
+
+                let reset_storage = ui.add(egui::Button::new("Reset Page"));
+                if reset_storage.clicked() {
+                    self.confirm_reset = true;
+                }
+
+                ui.horizontal(|ui| {
+                    let export_state = ui.add(egui::Button::new("Export State"));
+                    if export_state.clicked() {
+                        self.download_state(frame);
+                    }
+
+                    egui::ComboBox::from_id_salt("export_format")
+                        .selected_text(self.export_format.to_string())
+                        .show_ui(ui, |ui| {
+                            for format in ExportFormat::ALL {
+                                ui.selectable_value(
+                                    &mut self.export_format,
+                                    format,
+                                    format.to_string(),
+                                );
+                            }
+                        });
+                });
+
+                let import_state = ui.add(egui::Button::new("Import State"));
+                if import_state.clicked() {
+                    let sender = self.import.sender();
+                    let ctx = ctx.clone();
+
+                    fetch::spawn_local(async move {
+                        let state = match js_imports::pick_file().await {
+                            Ok(contents) => match contents.as_string() {
+                                Some(contents) => FetchState::Done(contents),
+                                // No file was picked.
+                                None => return,
+                            },
+                            Err(e) => FetchState::Failed(format!("{e:?}")),
+                        };
+
+                        let _ = sender.send(state);
+                        ctx.request_repaint();
+                    });
+                }
+
+                let reload_app = ui.add(egui::Button::new("Reload App"));
+                if reload_app.clicked() {
+                    // Unsaved changes would be lost if storage is unavailable.
+                    match self.flush_save(frame) {
+                        true => js_imports::reload(),
+                        false => self.confirm_reload = true,
+                    }
+                }
+
+                let reset_memory = ui.add(egui::Button::new("Reset egui Memory"));
+                if reset_memory.clicked() {
+                    // The options hold the theme & styles, which are managed by the app.
+                    ctx.memory_mut(|memory| {
+                        let options = memory.options.clone();
+                        *memory = Default::default();
+                        memory.options = options;
+                    });
+                    log::info!("Reset egui memory.");
+                }
+
+                ui.separator();
+                ui.label("Layout Options:");
 
                 let is_mobile = ui.add(egui::Button::new("Is Mobile?"));
                 let toggle_layout = ui.add(egui::Button::new("Toggle Layout"));
@@ -410,86 +2604,624 @@ impl eframe::App for MyApp {
                     log::info!("Mobile: {}", self.layout() == Layout::Mobile);
                 }
                 if toggle_layout.clicked() {
-                    self.layout = match self.layout() == Layout::Mobile {
-                        true => LayoutData::Desktop {},
-                        false => LayoutData::Mobile { tabs_open: false },
-                    };
-                    log::info!("New Layout: {}", self.layout());
+                    self.toggle_layout(frame);
+                }
+                if reset_layout.clicked() {
+                    let is_mobile = js_imports::is_mobile();
+                    self.auto_layout = true;
+
+                    self.set_layout(match is_mobile {
+                        false => LayoutData::Desktop {},
+                        true => LayoutData::Mobile {},
+                    });
+                    self.save_layout(frame);
+
+                    log::info!("Default Layout: {}", self.layout.kind());
+                }
+
+                // Only changes what is rendered, the stored layout is kept.
+                egui::ComboBox::from_label("Force Layout")
+                    .selected_text(match self.force_layout {
+                        Some(layout) => layout.to_string(),
+                        None => "Off".to_owned(),
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.force_layout, None, "Off");
+                        for layout in Layout::all().iter().copied() {
+                            ui.selectable_value(
+                                &mut self.force_layout,
+                                Some(layout),
+                                layout.to_string(),
+                            );
+                        }
+                    });
+
+                ui.checkbox(&mut self.remember_debug, "Remember Debug Window")
+                    .on_hover_text("Otherwise the debug window is closed when the app starts.");
+
+                ui.label(format!("Repaint Throttled: {}", self.throttle.throttled()));
+                ui.checkbox(&mut self.windows.perf, "Show Frame Time");
+
+                ui.separator();
+                ui.label("Home Page Url:");
+                self.home_url_ui(ui);
+
+                ui.separator();
+                ui.label("Idle Options:");
+                self.idle.options_ui(ui);
+
+                if DEVELOPER_MODE {
+                    ui.separator();
+                    ui.label("Force Profile State:");
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Loading").clicked() {
+                            self.profile.force(FetchState::Loading);
+                        }
+                        if ui.button("Failed").clicked() {
+                            self.profile
+                                .force(FetchState::Failed("Forced failure.".to_owned()));
+                        }
+                        if ui.button("Done").clicked() {
+                            self.profile.force(FetchState::Done(Profile::sample()));
+                        }
+                    });
                 }
-                if reset_layout.clicked() {
-                    let is_mobile = js_imports::is_mobile();
 
-                    self.layout = match is_mobile {
-                        false => LayoutData::Desktop {},
-                        true => LayoutData::Mobile { tabs_open: false },
-                    };
+                ui.separator();
+                ui.label("Environment:");
+                ui.label(format!("User Agent: {}", js_imports::user_agent()));
+                ui.label(format!("Platform: {}", js_imports::platform()));
+                if ui.button("Report Issue").clicked() {
+                    js_imports::open_url(&self.issue_url());
+                }
 
-                    log::info!("Default Layout: {}", self.layout());
+                ui.separator();
+                ui.label("Shortcuts:");
+                for (shortcut, description) in console::SHORTCUTS {
+                    ui.label(format!("{shortcut}: {description}"));
                 }
 
                 ui.separator();
                 ui.label("Log Output:");
-                // Concats log messages
-                let mut collect = self.logs.iter().fold("".to_owned(), |acc, log| acc + log);
-                ui.add(egui::TextEdit::multiline(&mut collect));
+
+                ui.horizontal(|ui| {
+                    ui.label("Capacity:");
+                    let mut capacity = self.log_capacity;
+                    let capacity_input =
+                        ui.add(egui::DragValue::new(&mut capacity).range(LOG_CAPACITY_RANGE));
+                    if capacity_input.changed() {
+                        self.set_log_capacity(capacity);
+                    }
+                });
+
+                if ui.button("Copy Logs").clicked() {
+                    let text: String =
+                        shown_logs(&self.logs, self.display_filter, &self.log_search)
+                            .map(|entry| entry.format(self.log_format) + "\n")
+                            .collect();
+
+                    fetch::spawn_local(async move {
+                        if let Err(e) = js_imports::copy_to_clipboard(&text).await {
+                            log::warn!("Unable to copy logs, the clipboard is unavailable: {e:?}");
+                        }
+                    });
+                }
+
+                if let Some(filter) = logger::installed_filter() {
+                    let mut level = filter.level();
+                    egui::ComboBox::from_label("Logged Levels")
+                        .selected_text(level.to_string())
+                        .show_ui(ui, |ui| {
+                            for option in log::LevelFilter::iter() {
+                                ui.selectable_value(&mut level, option, option.to_string());
+                            }
+                        });
+                    if level != filter.level() {
+                        log::info!("Logger level changed: {} -> {level}", filter.level());
+                        filter.set_level(level);
+                        self.log_level = level;
+                    }
+                }
+
+                egui::ComboBox::from_label("Shown Levels")
+                    .selected_text(self.display_filter.to_string())
+                    .show_ui(ui, |ui| {
+                        for filter in [
+                            log::LevelFilter::Off,
+                            log::LevelFilter::Error,
+                            log::LevelFilter::Warn,
+                            log::LevelFilter::Info,
+                            log::LevelFilter::Debug,
+                            log::LevelFilter::Trace,
+                        ] {
+                            ui.selectable_value(
+                                &mut self.display_filter,
+                                filter,
+                                filter.to_string(),
+                            );
+                        }
+                    });
+
+                egui::ComboBox::from_label("Log Format")
+                    .selected_text(self.log_format.to_string())
+                    .show_ui(ui, |ui| {
+                        for format in LogFormat::ALL {
+                            ui.selectable_value(&mut self.log_format, format, format.to_string());
+                        }
+                    });
+
+                ui.horizontal(|ui| {
+                    ui.label("Search:");
+                    ui.text_edit_singleline(&mut self.log_search);
+                });
+
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.log_wrap, "Wrap");
+                    ui.checkbox(&mut self.log_compact, "Compact");
+                    ui.checkbox(&mut self.log_autoscroll, "Follow Newest")
+                        .on_hover_text(
+                            "Scrolling down stops following, scrolling to the top resumes it.",
+                        );
+                    ui.label("Max length:");
+                    ui.add(egui::DragValue::new(&mut self.log_display_len).range(16..=65536));
+                });
+
+                self.log_pane(ui);
             });
         }
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            match &mut self.page_data {
-                PageData::Example(Example { label, value }) => {
-                    // The central panel the region left after adding TopPanel's and SidePanel's
-                    ui.heading("eframe template");
+        if self.windows.console {
+            self.console_ui(ctx, frame);
+        }
+
+        if let Some(action) = self.palette.show(ctx) {
+            self.run_action(action, frame);
+        }
+
+        if self.confirm_reload {
+            egui::Window::new("Reload App?")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label("Storage is unavailable, so any unsaved changes will be lost.");
 
                     ui.horizontal(|ui| {
-                        ui.label("Write something: ");
-                        ui.text_edit_singleline(label);
+                        if ui.button("Reload").clicked() {
+                            js_imports::reload();
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.confirm_reload = false;
+                        }
                     });
+                });
+        }
 
-                    ui.add(egui::Slider::new(value, 0.0..=10.0).text("value"));
-                    if ui.button("Increment").clicked() {
-                        *value += 1.0;
-                    }
+        if let Some((format, state)) = &self.confirm_export {
+            let mut downloaded = false;
+            let mut cancelled = false;
+
+            egui::Window::new("Download Large Export?")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(format!(
+                        "The exported state is {}, which may be slow to download & open.",
+                        format_size(state.len())
+                    ));
 
-                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui.button("Download").clicked() {
+                            js_imports::download(format.file_name(), state);
+                            downloaded = true;
+                        }
+                        cancelled = ui.button("Cancel").clicked();
+                    });
+                });
 
-                    ui.add(egui::github_link_file!(
-                        "https://github.com/emilk/eframe_template/blob/main/",
-                        "Source code."
-                    ));
+            if downloaded || cancelled {
+                self.confirm_export = None;
+            }
+            if downloaded {
+                self.push_toast(log::Level::Info, "Exported app state.");
+            }
+        }
+
+        if self.confirm_reset {
+            egui::Window::new("Reset Pages?")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label("The saved data will be replaced with the defaults.");
 
-                    ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
-                        powered_by_egui_and_eframe(ui);
-                        egui::warn_if_debug_build(ui);
+                    ui.horizontal(|ui| {
+                        if ui.button("Reset This Page").clicked() {
+                            let page = self.page();
+                            let saved = page.reset(frame);
+                            self.report_save(saved);
+
+                            self.page_data = page.load(frame);
+                            self.dirty = false;
+                            self.confirm_reset = false;
+                            self.push_toast(log::Level::Info, format!("Reset the {page} page."));
+                        }
+                        if ui.button("Reset All").clicked() {
+                            // Overwrites the page saved data with default values.
+                            for page in Page::all().to_owned() {
+                                let saved = page.reset(frame);
+                                self.report_save(saved);
+                            }
+
+                            // Sets the current page to its default.
+                            self.page_data = self.page().load(frame);
+                            self.dirty = false;
+                            self.confirm_reset = false;
+                            self.push_toast(log::Level::Info, "Reset all pages.");
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.confirm_reset = false;
+                        }
                     });
-                }
-                PageData::Home => {
-                    egui_commonmark::commonmark_str!(
+                });
+        }
+
+        if self.whats_new {
+            egui::Window::new("What's new")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    egui_commonmark::CommonMarkViewer::new().show(
                         ui,
-                        &mut Default::default(),
-                        "assets/markdown/home.md"
+                        &mut self.md_cache,
+                        changelog_for(MyApp::version()),
+                    );
+
+                    ui.separator();
+
+                    if ui.button("Close").clicked() {
+                        self.whats_new = false;
+                    }
+                });
+        }
+
+        if self.safe_mode {
+            egui::TopBottomPanel::bottom("safe_mode_banner").show(ctx, |ui| {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    "Safe mode: started with default settings & nothing will be saved. \
+                    Remove \"?safe=1\" from the url to leave safe mode.",
+                );
+            });
+        }
+
+        if self.logger_warning {
+            egui::TopBottomPanel::bottom("logger_banner").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        "The logger couldn't be installed, so the log pane is disabled.",
                     );
+                    if ui.button("Dismiss").clicked() {
+                        self.logger_warning = false;
+                    }
+                });
+            });
+        }
+
+        if self.ephemeral {
+            egui::TopBottomPanel::bottom("ephemeral_banner").show(ctx, |ui| {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    "Storage is unavailable, so nothing will be saved.",
+                );
+            });
+        }
+
+        // Clicking a toast opens the log pane, at the log it came from.
+        if let Some(clicked) = toast::show(ctx, &mut self.toasts) {
+            self.windows.debug = true;
+            if let Some(log_id) = clicked.log_id {
+                self.focus_log(log_id);
+            }
+        }
+
+        let panel_frame = tint::central_panel(&ctx.style(), self.page_data.tint());
+        let central_panel = egui::CentralPanel::default().frame(panel_frame);
+        let mut settings_changed = false;
+        let mut go_home = false;
+
+        let page = self.page();
+        let mut scroll_area = egui::ScrollArea::vertical().auto_shrink(false);
+        if let Some(offset) = self.restore_scroll.take() {
+            scroll_area = scroll_area.vertical_scroll_offset(offset);
+        }
+
+        // Fades & slides the page in after switching, unless the user prefers reduced motion.
+        let transition_id = egui::Id::new("page_transition");
+        if std::mem::take(&mut self.page_switched) && !self.reduced_motion {
+            ctx.animate_value_with_time(transition_id, 0.0, 0.0);
+        }
+        let transition = ctx.animate_value_with_time(transition_id, 1.0, PAGE_TRANSITION);
+
+        central_panel.show(ctx, |ui| {
+            ui.set_opacity(transition);
+            ui.add_space((1.0 - transition) * PAGE_TRANSITION_SLIDE);
+
+            // Shows which page is open, as not every page has a heading.
+            let current = match self
+                .dynamic_page
+                .and_then(|index| self.dynamic_pages.get(index))
+            {
+                Some(dynamic_page) => dynamic_page.name.clone(),
+                None => page_label(page),
+            };
+            ui.horizontal(|ui| {
+                let home = ui
+                    .link(Page::Home.icon())
+                    .on_hover_text("Go to the home page");
+                go_home = home.clicked() && (page != Page::Home || self.dynamic_page.is_some());
+                ui.label("›");
+                ui.strong(current);
+            });
+            ui.separator();
+
+            let dynamic_page = self
+                .dynamic_page
+                .and_then(|index| self.dynamic_pages.get_mut(index));
+            if let Some(page) = dynamic_page {
+                scroll_area.show(ui, |ui| page.view.ui(ui, ctx, frame));
+                return;
+            }
+
+            // Settings also changeable elsewhere are kept in sync with the app.
+            if let PageData::Settings(settings) = &mut self.page_data {
+                settings.theme = ctx.options(|o| o.theme_preference);
+                settings.accent = self.accent;
+                settings.log_capacity = self.log_capacity;
+                settings.lang = i18n::lang();
+            }
+
+            let remote = match (&self.home_url, self.home_markdown.state()) {
+                (Some(_), FetchState::Done(markdown)) => Some(markdown.as_str()),
+                _ => None,
+            };
+            let scroll = scroll_area.show(ui, |ui| match &mut self.page_data {
+                PageData::Home => HomeView {
+                    online: self.online,
+                    doc: &mut self.home_doc,
+                    remote,
+                    md_cache: &mut self.md_cache,
+                    profile: &self.profile,
+                    profile_requested: &mut self.profile_requested,
+                }
+                .ui(ui, ctx, frame),
+                PageData::Example(example) => example.ui(ui, ctx, frame),
+                PageData::Settings(settings) => settings.ui(ui, ctx, frame),
+                PageData::NotFound { attempted } => NotFoundView {
+                    attempted,
+                    go_home: &mut go_home,
+                }
+                .ui(ui, ctx, frame),
+            });
+            self.scroll_offsets.insert(page, scroll.state.offset.y);
+
+            match page {
+                // Saved when the page is switched.
+                Page::Example => {
+                    self.dirty |= scroll.inner;
+                    self.link_outdated |= scroll.inner;
                 }
+                // Saved as soon as they change.
+                Page::Settings => settings_changed = scroll.inner,
+                Page::Home | Page::NotFound => {}
             }
         });
 
-        // Updates the log buffer
-        let log = match &self.log_receiver {
-            Some(receiver) => match receiver.try_recv() {
-                Ok(log) => Some(log),
-                Err(_) => None,
-            },
-            None => None,
+        if go_home {
+            let saved = self.switch_page(Page::Home, frame);
+            self.report_save(saved);
+        }
+
+        // Waits until a drag ends, so the url isn't replaced every frame.
+        if self.link_outdated && !ctx.input(|i| i.pointer.any_down()) {
+            js_imports::set_hash(&self.page_hash());
+            self.link_outdated = false;
+        }
+
+        if let (true, PageData::Settings(settings)) = (settings_changed, &self.page_data) {
+            log::debug!("Settings changed: {settings:?}");
+            ctx.set_theme(settings.theme);
+            self.theme = Some(settings.theme);
+            self.accent = settings.accent;
+            apply_accent(ctx, self.accent);
+            self.lang = Some(settings.lang);
+            i18n::set_lang(settings.lang);
+            let log_capacity = settings.log_capacity;
+            self.set_log_capacity(log_capacity);
+            let saved = self.save_page(frame);
+            self.report_save(saved);
+        }
+
+        let logged = self.drain_logs();
+        if logged {
+            // Wakes up to log the repeats of a collapsed message once its window expires.
+            ctx.request_repaint_after(Duration::from_secs_f64(logger::REPEAT_WINDOW / 1000.0));
+        }
+
+        self.idle.show_overlay(ctx);
+
+        self.perf.update(ctx);
+        if self.windows.perf {
+            self.perf.show(ctx, self.throttle.throttled());
+        }
+
+        // The frame time is only meaningful while repainting continuously.
+        let needs_repaint = fetched
+            || logged
+            || self.windows.perf
+            || self.layout_pending.is_some()
+            || transition < 1.0;
+        self.throttle.update(ctx, needs_repaint);
+
+        if !self.rendered {
+            self.rendered = true;
+            js_imports::hide_splash();
+        }
+    }
+}
+
+/// Sets the colour of selections & links in both themes, or resets them if there is no accent.
+fn apply_accent(ctx: &egui::Context, accent: Option<egui::Color32>) {
+    ctx.all_styles_mut(|style| {
+        let defaults = match style.visuals.dark_mode {
+            true => egui::Visuals::dark(),
+            false => egui::Visuals::light(),
         };
 
-        if let Some((level, text)) = log {
-            self.logs.push(format!("{}: {}\n", level, text));
+        style.visuals.selection.bg_fill = accent.unwrap_or(defaults.selection.bg_fill);
+        style.visuals.hyperlink_color = accent.unwrap_or(defaults.hyperlink_color);
+    });
+}
+
+/// Disables animations in the style if the user prefers reduced motion, otherwise restores them.
+fn apply_motion_preference(style: &mut egui::Style, reduced_motion: bool) {
+    style.animation_time = match reduced_motion {
+        true => 0.0,
+        false => egui::Style::default().animation_time,
+    };
+}
+
+/// Shortens a log longer than `max_len` characters, noting its original length.
+///
+/// Returns `None` if the log is short enough to be shown in full.
+fn shorten_log(entry: &str, max_len: usize) -> Option<String> {
+    let len = entry.chars().count();
+    if len <= max_len {
+        return None;
+    }
+
+    let short: String = entry.chars().take(max_len).collect();
+    Some(format!("{short}… ({len} characters)"))
+}
+
+/// Gets the label the page is shown with in the navigation.
+fn page_label(page: Page) -> String {
+    // Untranslated pages use the title from their metadata.
+    i18n::get(&format!("nav.{}", page.fragment()))
+        .or(page.meta().title)
+        .unwrap_or_else(|| page.label().to_owned())
+}
+
+/// Formats a size in bytes, in the largest unit it has a whole number of.
+fn format_size(bytes: usize) -> String {
+    match bytes {
+        0..=1023 => format!("{bytes} B"),
+        1024..=1_048_575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+/// Percent-encodes the text, so it can be used in a url query string.
+fn url_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Decodes percent-encoded text from a url query string.
+///
+/// Invalid escapes are kept as they are.
+fn url_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = bytes
+            .get(index + 1..index + 3)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[index], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                index += 1;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                index += 1;
+            }
         }
     }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Removes blank lines and trailing whitespace from a log, so more fit in the log pane.
+fn compact_log(entry: &str) -> String {
+    entry
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Gets the logs shown in the log pane, newest first.
+///
+/// Only logs whose message or target contains the search query are shown, ignoring case.
+fn shown_logs<'a>(
+    logs: &'a CircularQueue<LogType>,
+    filter: log::LevelFilter,
+    search: &str,
+) -> impl Iterator<Item = &'a LogType> {
+    let search = search.to_lowercase();
+    logs.iter()
+        .filter(move |entry| log_shown(entry, filter, &search))
+}
+
+/// Whether a log passes the level filter & contains the lowercase search.
+fn log_shown(entry: &LogType, filter: log::LevelFilter, search: &str) -> bool {
+    entry.level <= filter
+        && (search.is_empty()
+            || entry.message.to_lowercase().contains(search)
+            || entry.target.to_lowercase().contains(search))
+}
+
+/// Gets the section of the changelog for the given version.
+///
+/// If the version has no section then the whole changelog is returned.
+fn changelog_for(version: &str) -> &'static str {
+    let heading = format!("## {version}");
+
+    let Some(start) = CHANGELOG.find(&heading) else {
+        return CHANGELOG;
+    };
+    let section = &CHANGELOG[start..];
+
+    // The section ends at the next version heading.
+    match section[heading.len()..].find("\n## ") {
+        Some(end) => &section[..heading.len() + end],
+        None => section,
+    }
 }
 
 fn powered_by_egui_and_eframe(ui: &mut egui::Ui) {
-    ui.horizontal(|ui| {
+    // Wraps between the labels on narrow screens, instead of clipping.
+    ui.horizontal_wrapped(|ui| {
         ui.spacing_mut().item_spacing.x = 0.0;
         ui.label("Powered by ");
         ui.hyperlink_to("egui", "https://github.com/emilk/egui");
@@ -501,3 +3233,419 @@ fn powered_by_egui_and_eframe(ui: &mut egui::Ui) {
         ui.label(".");
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use eframe::Storage as _;
+
+    use super::*;
+
+    /// Storage kept in memory, so tests don't need a browser.
+    #[derive(Default, Clone, PartialEq, Debug)]
+    struct MemoryStorage(HashMap<String, String>);
+
+    impl eframe::Storage for MemoryStorage {
+        fn get_string(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+
+        fn set_string(&mut self, key: &str, value: String) {
+            self.0.insert(key.to_owned(), value);
+        }
+
+        fn flush(&mut self) {}
+    }
+
+    impl Host for MemoryStorage {
+        fn storage(&self) -> Option<&dyn eframe::Storage> {
+            Some(self)
+        }
+
+        fn storage_mut(&mut self) -> Option<&mut (dyn eframe::Storage + 'static)> {
+            Some(self)
+        }
+    }
+
+    /// Runs a pass of the app headless, returning the accessibility nodes it displayed.
+    fn run_pass(
+        ctx: &egui::Context,
+        app: &mut MyApp,
+        host: &mut MemoryStorage,
+        events: Vec<egui::Event>,
+    ) -> Vec<(egui::accesskit::NodeId, egui::accesskit::Node)> {
+        let input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::Pos2::ZERO,
+                egui::vec2(1280.0, 800.0),
+            )),
+            events,
+            ..Default::default()
+        };
+        let output = ctx.run(input, |ctx| app.show(ctx, host));
+
+        output
+            .platform_output
+            .accesskit_update
+            .map(|update| update.nodes)
+            .unwrap_or_default()
+    }
+
+    /// Finds the id of a node with the given role, whose name ends with the text.
+    fn find_node(
+        nodes: &[(egui::accesskit::NodeId, egui::accesskit::Node)],
+        role: egui::accesskit::Role,
+        name: &str,
+    ) -> Option<egui::accesskit::NodeId> {
+        nodes
+            .iter()
+            .find(|(_, node)| {
+                node.role() == role && node.name().is_some_and(|label| label.ends_with(name))
+            })
+            .map(|(id, _)| *id)
+    }
+
+    /// Creates example data with the given label.
+    fn example(label: &str) -> Example {
+        Example {
+            label: label.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Gets the example data that is loaded from the storage.
+    fn load_example(storage: &MemoryStorage) -> Example {
+        match Page::Example.load_from(Some(storage)) {
+            PageData::Example(example) => example,
+            other => panic!("Loaded {other:?} instead of example data."),
+        }
+    }
+
+    /// Creates a log with the given level & message.
+    fn log_entry(level: log::Level, message: &str) -> LogType {
+        LogType {
+            level,
+            target: "tye_home::test".to_owned(),
+            module_path: None,
+            message: message.to_owned(),
+            time: 0.0,
+            count: 1,
+        }
+    }
+
+    /// Gets the messages in the app's log buffer, oldest first.
+    fn log_messages(app: &MyApp) -> Vec<&str> {
+        app.logs().map(|entry| entry.message.as_str()).collect()
+    }
+
+    #[test]
+    fn handled_logs_are_buffered() {
+        let mut app = MyApp::new_in_memory();
+        app.handle_log(log_entry(log::Level::Info, "first"));
+        app.handle_log(log_entry(log::Level::Debug, "second"));
+
+        assert_eq!(log_messages(&app), ["first", "second"]);
+        assert!(app.toasts.is_empty());
+    }
+
+    #[test]
+    fn repeated_logs_are_counted() {
+        let mut app = MyApp::new_in_memory();
+        for message in ["first", "repeated", "repeated", "repeated", "last"] {
+            app.handle_log(log_entry(log::Level::Info, message));
+        }
+
+        assert_eq!(log_messages(&app), ["first", "repeated", "last"]);
+        let counts: Vec<usize> = app.logs().map(|entry| entry.count).collect();
+        assert_eq!(counts, [1, 3, 1]);
+        assert_eq!(app.next_log_id, 3);
+    }
+
+    #[test]
+    fn repeated_logs_of_other_levels_are_kept() {
+        let mut app = MyApp::new_in_memory();
+        app.handle_log(log_entry(log::Level::Info, "message"));
+        app.handle_log(log_entry(log::Level::Warn, "message"));
+
+        assert_eq!(log_messages(&app), ["message", "message"]);
+    }
+
+    #[test]
+    fn logs_past_the_logged_levels_are_dropped() {
+        let mut app = MyApp::new_in_memory();
+        app.log_level = log::LevelFilter::Info;
+        app.handle_log(log_entry(log::Level::Debug, "debug"));
+        app.handle_log(log_entry(log::Level::Info, "info"));
+        app.handle_log(log_entry(log::Level::Error, "error"));
+
+        assert_eq!(log_messages(&app), ["info", "error"]);
+
+        app.log_level = log::LevelFilter::Off;
+        app.handle_log(log_entry(log::Level::Error, "dropped"));
+        assert_eq!(log_messages(&app), ["info", "error"]);
+        assert_eq!(app.toasts.len(), 1);
+    }
+
+    #[test]
+    fn oldest_logs_are_evicted() {
+        let mut app = MyApp::new_in_memory();
+        app.set_log_capacity(2);
+        for message in ["first", "second", "third"] {
+            app.handle_log(log_entry(log::Level::Info, message));
+        }
+
+        assert_eq!(log_messages(&app), ["second", "third"]);
+    }
+
+    #[test]
+    fn error_logs_create_toasts() {
+        let mut app = MyApp::new_in_memory();
+        app.handle_log(log_entry(log::Level::Warn, "warning"));
+        app.handle_log(log_entry(log::Level::Error, "error"));
+
+        assert_eq!(app.toasts.len(), 1);
+        assert_eq!(app.toasts[0].level, log::Level::Error);
+        assert_eq!(app.toasts[0].message, "error");
+        assert_eq!(app.toasts[0].log_id, Some(1));
+    }
+
+    #[test]
+    fn identical_toasts_are_merged() {
+        let mut app = MyApp::new_in_memory();
+        app.handle_log(log_entry(log::Level::Error, "error"));
+        app.handle_log(log_entry(log::Level::Error, "other"));
+        app.handle_log(log_entry(log::Level::Error, "error"));
+
+        assert_eq!(app.toasts.len(), 2);
+        // The merged toast is the newest, & leads to the newest log.
+        assert_eq!(app.toasts[1].message, "error");
+        assert_eq!(app.toasts[1].count, 2);
+        assert_eq!(app.toasts[1].log_id, Some(2));
+    }
+
+    #[test]
+    fn toasts_are_capped() {
+        let mut app = MyApp::new_in_memory();
+        for index in 0..toast::MAX_TOASTS + 2 {
+            app.handle_log(log_entry(log::Level::Error, &format!("error {index}")));
+        }
+
+        assert_eq!(app.toasts.len(), toast::MAX_TOASTS);
+        assert_eq!(app.toasts[0].message, "error 2");
+    }
+
+    #[test]
+    fn focusing_a_log_clears_the_search() {
+        let mut app = MyApp::new_in_memory();
+        app.log_search = "unrelated".to_owned();
+        app.focus_log(3);
+
+        assert_eq!(app.log_jump, Some(3));
+        assert!(app.log_search.is_empty());
+        assert!(!app.log_autoscroll);
+    }
+
+    #[test]
+    fn reduced_motion_disables_animations() {
+        let mut style = egui::Style::default();
+        let animation_time = style.animation_time;
+        assert!(animation_time > 0.0);
+
+        apply_motion_preference(&mut style, true);
+        assert_eq!(style.animation_time, 0.0);
+
+        apply_motion_preference(&mut style, false);
+        assert_eq!(style.animation_time, animation_time);
+    }
+
+    #[test]
+    fn navigates_to_example_page() {
+        use egui::accesskit::{Action, ActionRequest, Role};
+
+        let ctx = egui::Context::default();
+        ctx.enable_accesskit();
+        let mut app = MyApp::new_in_memory();
+        let mut host = MemoryStorage::default();
+
+        let nodes = run_pass(&ctx, &mut app, &mut host, Vec::new());
+        assert!(find_node(&nodes, Role::Label, "eframe template").is_none());
+        let button = find_node(&nodes, Role::Button, "Example")
+            .expect("The navigation should have an Example button.");
+
+        let click = egui::Event::AccessKitActionRequest(ActionRequest {
+            action: Action::Default,
+            target: button,
+            data: None,
+        });
+        run_pass(&ctx, &mut app, &mut host, vec![click]);
+        let nodes = run_pass(&ctx, &mut app, &mut host, Vec::new());
+
+        assert_eq!(app.page(), Page::Example);
+        assert!(find_node(&nodes, Role::Label, "eframe template").is_some());
+        assert!(find_node(&nodes, Role::Slider, "value").is_some());
+    }
+
+    #[test]
+    fn migrates_unversioned_app_state() {
+        let raw = r#"(
+            page_data: Example((label: "Old")),
+            debug_window: true,
+            layout: Tablet(),
+        )"#;
+        let app = MyApp::migrate_ron(raw).expect("The state should be readable.");
+
+        assert_eq!(app.version, APP_VERSION);
+        assert!(app.windows.debug);
+        assert!(!app.windows.console);
+        assert_eq!(app.layout.kind(), Layout::Tablet);
+        match app.page_data {
+            PageData::Example(example) => assert_eq!(example.label, "Old"),
+            other => panic!("Migrated {other:?} instead of example data."),
+        }
+    }
+
+    #[test]
+    fn migrates_versioned_app_state() {
+        let raw = serde_json::json!({
+            "version": 1,
+            "page_data": { "Settings": {} },
+            "console": true,
+            "show_perf": true,
+        });
+        let app = MyApp::migrate(raw).expect("The state should be readable.");
+
+        assert_eq!(app.version, APP_VERSION);
+        assert!(!app.windows.debug);
+        assert!(app.windows.console);
+        assert!(app.windows.perf);
+        assert_eq!(app.page(), Page::Settings);
+    }
+
+    #[test]
+    fn all_page_data_does_not_modify_storage() {
+        let mut storage = MemoryStorage::default();
+        let page_data = PageData::Example(example("Stored"));
+        eframe::set_value(&mut storage, page_storage_key!(Page::Example), &page_data);
+        // Corrupt data isn't backed up, as that would modify storage.
+        storage.set_string(page_storage_key!(Page::Settings), "corrupt".to_owned());
+        let before = storage.clone();
+
+        let all = MyApp::new_in_memory().all_page_data(Some(&storage));
+
+        assert_eq!(storage, before);
+        let pages: Vec<Page> = all.iter().map(|(page, _)| *page).collect();
+        assert_eq!(pages, [Page::Home, Page::Example, Page::Settings]);
+        for (page, page_data) in all {
+            assert_eq!(page_data.kind(), page);
+            if let PageData::Example(example) = page_data {
+                assert_eq!(example.label, "Stored");
+            }
+        }
+    }
+
+    #[test]
+    fn unsaved_pages_have_default_data() {
+        let all = MyApp::new_in_memory().all_page_data(Some(&MemoryStorage::default()));
+
+        for (page, page_data) in all {
+            assert_eq!(page_data.kind(), page);
+            if let PageData::Example(example) = page_data {
+                assert_eq!(example.label, Example::default().label);
+            }
+        }
+    }
+
+    #[test]
+    fn example_values_are_clamped() {
+        let migrate = |value: f32| {
+            Example {
+                value,
+                ..example("")
+            }
+            .migrate(Example::VERSION)
+        };
+
+        assert_eq!(migrate(-5.0).value, 0.0);
+        assert_eq!(migrate(50.0).value, 10.0);
+        assert_eq!(migrate(4.0).value, 4.0);
+        assert_eq!(migrate(f32::NAN).value, Example::default().value);
+    }
+
+    #[test]
+    fn exported_state_round_trips() {
+        for format in ExportFormat::ALL {
+            let mut storage = MemoryStorage::default();
+            PageData::Example(example("Exported"))
+                .save_to(Some(&mut storage))
+                .unwrap();
+            let mut app = MyApp::new_in_memory();
+            app.windows.console = true;
+            app.accent = Some(egui::Color32::RED);
+
+            let exported = app.export_state(Some(&storage), format).unwrap();
+            let mut imported = MemoryStorage::default();
+            MyApp::new_in_memory()
+                .import_state(&exported, Some(&mut imported))
+                .unwrap_or_else(|e| panic!("Unable to import {format}: {e}"));
+
+            assert_eq!(load_example(&imported).label, "Exported");
+            let state = imported.get_string(STORAGE_KEY).unwrap();
+            let state = MyApp::migrate(serde_json::from_str(&state).unwrap()).unwrap();
+            assert!(state.windows.console);
+            assert_eq!(
+                eframe::get_value::<Option<egui::Color32>>(&imported, ACCENT_KEY),
+                Some(Some(egui::Color32::RED))
+            );
+        }
+    }
+
+    #[test]
+    fn example_loads_unversioned_data() {
+        let mut storage = MemoryStorage::default();
+        let page_data = PageData::Example(example("Un-versioned"));
+        eframe::set_value(&mut storage, page_storage_key!(Page::Example), &page_data);
+
+        assert_eq!(load_example(&storage).label, "Un-versioned");
+    }
+
+    #[test]
+    fn example_loads_versioned_data() {
+        let mut storage = MemoryStorage::default();
+        let versioned = Versioned {
+            version: Example::VERSION,
+            data: PageData::Example(example("Versioned")),
+        };
+        eframe::set_value(&mut storage, page_storage_key!(Page::Example), &versioned);
+
+        assert_eq!(load_example(&storage).label, "Versioned");
+    }
+
+    #[test]
+    fn example_loads_compressed_data() {
+        let mut storage = MemoryStorage::default();
+        let versioned = Versioned {
+            version: Example::VERSION,
+            data: PageData::Example(example("Compressed")),
+        };
+        let json = serde_json::to_string(&versioned).unwrap();
+        storage.set_string(page_storage_key!(Page::Example), compress::compress(&json));
+
+        assert_eq!(load_example(&storage).label, "Compressed");
+    }
+
+    #[test]
+    fn corrupt_data_is_backed_up() {
+        let mut storage = MemoryStorage::default();
+        storage.set_string(page_storage_key!(Page::Example), "not ron".to_owned());
+
+        let (page_data, corrupt) = Page::Example.load_checked(Some(&storage));
+        assert!(matches!(page_data, PageData::Example(_)));
+        corrupt
+            .expect("The corrupt data should be returned.")
+            .back_up(&mut storage);
+
+        assert_eq!(
+            storage.get_string(&format!("{STORAGE_KEY}-Example-Corrupt")),
+            Some("not ron".to_owned())
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+/// Marks a stored value as compressed, so uncompressed values can still be read.
+const MAGIC: &str = "deflate:";
+
+/// The compression level, from 0 (none) to 10 (smallest).
+const LEVEL: u8 = 6;
+
+/// Compresses the text into a base64 string, prefixed with the magic header.
+pub fn compress(text: &str) -> String {
+    let compressed = miniz_oxide::deflate::compress_to_vec(text.as_bytes(), LEVEL);
+    format!("{MAGIC}{}", STANDARD.encode(compressed))
+}
+
+/// Decompresses text compressed with [`compress`].
+///
+/// Returns `None` if the value isn't compressed, or is corrupt.
+pub fn decompress(value: &str) -> Option<String> {
+    let encoded = value.strip_prefix(MAGIC)?;
+    let compressed = STANDARD
+        .decode(encoded)
+        .inspect_err(|e| log::warn!("Compressed value isn't valid base64: {e}"))
+        .ok()?;
+    let bytes = miniz_oxide::inflate::decompress_to_vec(&compressed)
+        .inspect_err(|e| log::warn!("Unable to decompress value: {e}"))
+        .ok()?;
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let text = r#"{"version":1,"data":{"Example":{"label":"Hello world! 🌍"}}}"#;
+        let compressed = compress(text);
+
+        assert!(compressed.starts_with(MAGIC));
+        assert_eq!(decompress(&compressed).as_deref(), Some(text));
+    }
+
+    #[test]
+    fn uncompressed_values_are_ignored() {
+        assert_eq!(decompress(r#"(version: 1, data: Home)"#), None);
+    }
+
+    #[test]
+    fn corrupt_values_are_ignored() {
+        assert_eq!(decompress("deflate:not base64!"), None);
+        // Valid base64, but not deflate data.
+        assert_eq!(decompress("deflate:AAAA"), None);
+    }
+}
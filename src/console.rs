@@ -0,0 +1,61 @@
+use crate::app::Page;
+
+/// The keyboard shortcuts available in the app, as (shortcut, description) pairs.
+pub const SHORTCUTS: &[(&str, &str)] = &[
+    ("`", "Toggle the console"),
+    ("Ctrl+D", "Toggle the debug window"),
+    ("Ctrl+K", "Open the command palette"),
+    ("Alt+1-9", "Switch to the page at that position"),
+    ("Ctrl+Plus/Minus", "Zoom in or out"),
+    ("Ctrl+0", "Reset the zoom"),
+    ("Ctrl+Scroll", "Zoom in or out"),
+];
+
+/// The commands that can be entered into the console, as (usage, description) pairs.
+pub const COMMANDS: &[(&str, &str)] = &[
+    ("help", "Lists the available commands"),
+    ("clear", "Clears the log buffer"),
+    ("debug", "Toggles the debug window"),
+    ("page <name>", "Switches to the named page"),
+];
+
+/// A command entered into the console.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Help,
+    Clear,
+    Debug,
+    Page(Page),
+}
+
+impl Command {
+    /// Parses a command from the console input.
+    ///
+    /// Returns a message describing the problem if the input is invalid.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut words = input.split_whitespace();
+
+        let command = match words.next() {
+            Some("help") => Command::Help,
+            Some("clear") => Command::Clear,
+            Some("debug") => Command::Debug,
+            Some("page") => {
+                let name = words.next().ok_or("Missing page name.")?;
+                let page = Page::all()
+                    .iter()
+                    .copied()
+                    .filter(|page| *page != Page::NotFound)
+                    .find(|page| page.to_string().eq_ignore_ascii_case(name))
+                    .ok_or_else(|| format!("Unknown page: {name}"))?;
+                Command::Page(page)
+            }
+            Some(command) => return Err(format!("Unknown command: {command}")),
+            None => return Err("Empty command.".to_owned()),
+        };
+
+        match words.next() {
+            Some(_) => Err(format!("Too many arguments: {input}")),
+            None => Ok(command),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::{fmt::Display, future::Future, sync::mpsc};
+
+/// The state of data that is fetched from the network.
+#[derive(Debug, Clone)]
+pub enum FetchState<T> {
+    /// The request is still in progress.
+    Loading,
+    /// The request failed for the contained reason.
+    Failed(String),
+    /// The request succeeded with the contained data.
+    Done(T),
+}
+
+/// Runs the request in the background, sending its progress through the sender.
+///
+/// This is the single place request errors are handled, so each request only returns its errors.
+/// Panics can't be caught on the web, as they abort the whole app, so requests must not panic.
+pub fn spawn<T, E>(
+    name: &'static str,
+    request: impl Future<Output = Result<T, E>> + 'static,
+    sender: mpsc::Sender<FetchState<T>>,
+    ctx: egui::Context,
+) where
+    T: 'static,
+    E: Display,
+{
+    log::debug!("Fetching {name}.");
+    let _ = sender.send(FetchState::Loading);
+
+    spawn_local(async move {
+        let state = match request.await {
+            Ok(data) => {
+                log::debug!("Fetched {name}.");
+                FetchState::Done(data)
+            }
+            Err(e) => {
+                log::error!("Failed to fetch {name}: {e}");
+                FetchState::Failed(e.to_string())
+            }
+        };
+
+        if sender.send(state).is_err() {
+            log::warn!("Unable to send {name} to application.");
+        }
+        ctx.request_repaint();
+    });
+}
+
+/// Runs the future in the background of the current thread.
+#[cfg(target_arch = "wasm32")]
+pub fn spawn_local(future: impl Future<Output = ()> + 'static) {
+    wasm_bindgen_futures::spawn_local(future);
+}
+
+/// Runs the future to completion, as there is no background executor natively.
+///
+/// This blocks the app until the future is done.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn spawn_local(future: impl Future<Output = ()> + 'static) {
+    match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime.block_on(future),
+        Err(e) => log::error!("Unable to start a runtime for a background task: {e}"),
+    }
+}
+
+/// Holds the latest [`FetchState`] of some network-backed data.
+///
+/// Async tasks update the state through the channel from [`Fetch::sender`].
+pub struct Fetch<T> {
+    state: FetchState<T>,
+    sender: mpsc::Sender<FetchState<T>>,
+    receiver: mpsc::Receiver<FetchState<T>>,
+}
+
+impl<T> Default for Fetch<T> {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            state: FetchState::Loading,
+            sender,
+            receiver,
+        }
+    }
+}
+
+impl<T> Fetch<T> {
+    /// Creates a new [`Fetch`] with the given initial state.
+    pub fn new(state: FetchState<T>) -> Self {
+        Self {
+            state,
+            ..Default::default()
+        }
+    }
+
+    /// Gets the current [`FetchState`].
+    pub fn state(&self) -> &FetchState<T> {
+        &self.state
+    }
+
+    /// Gets a sender that updates the [`FetchState`].
+    pub fn sender(&self) -> mpsc::Sender<FetchState<T>> {
+        self.sender.clone()
+    }
+
+    /// Sends the given state through the same channel as the real requests.
+    pub fn force(&self, state: FetchState<T>) {
+        // Can't fail, as the receiver is owned by self.
+        let _ = self.sender.send(state);
+    }
+
+    /// Updates the [`FetchState`] with any received states.
+    ///
+    /// Returns whether the state changed.
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok(state) = self.receiver.try_recv() {
+            self.state = state;
+            changed = true;
+        }
+        changed
+    }
+}
@@ -0,0 +1,139 @@
+/// Metadata for a content page, parsed from the front matter of its markdown.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PageMeta {
+    /// The label for the page in the navigation.
+    pub title: Option<String>,
+    /// Where the page is positioned in the navigation, lowest first.
+    pub order: i32,
+    /// Whether the page is unfinished.
+    pub draft: bool,
+    /// Whether the page is excluded from the navigation.
+    pub hidden: bool,
+}
+
+/// Splits the `---` delimited front matter from the given markdown.
+///
+/// Returns the parsed metadata & the markdown without the front matter.
+/// Markdown without front matter is returned unchanged, with the default metadata.
+pub fn parse(markdown: &str) -> (PageMeta, &str) {
+    let Some(rest) = markdown
+        .strip_prefix("---\n")
+        .or_else(|| markdown.strip_prefix("---\r\n"))
+    else {
+        return (PageMeta::default(), markdown);
+    };
+
+    // Finds the closing delimiter line.
+    let mut offset = 0;
+    let mut split = None;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            split = Some((&rest[..offset], &rest[offset + line.len()..]));
+            break;
+        }
+        offset += line.len();
+    }
+
+    let Some((header, body)) = split else {
+        log::warn!("Unterminated front matter, rendering it as markdown.");
+        return (PageMeta::default(), markdown);
+    };
+
+    let mut meta = PageMeta::default();
+    for line in header.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once(':') else {
+            log::warn!("Malformed front matter line: {line}");
+            continue;
+        };
+        let value = value.trim().trim_matches('"');
+
+        match key.trim() {
+            "title" => meta.title = Some(value.to_owned()),
+            "order" => match value.parse() {
+                Ok(order) => meta.order = order,
+                Err(_) => log::warn!("Invalid front matter order: {value}"),
+            },
+            "draft" => meta.draft = parse_bool(value),
+            "hidden" => meta.hidden = parse_bool(value),
+            key => log::warn!("Unknown front matter key: {key}"),
+        }
+    }
+
+    (meta, body)
+}
+
+/// Parses a front matter boolean, treating invalid values as false.
+fn parse_bool(value: &str) -> bool {
+    match value {
+        "true" => true,
+        "false" => false,
+        _ => {
+            log::warn!("Invalid front matter boolean: {value}");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_front_matter() {
+        let (meta, body) = parse("---\ntitle: \"About\"\norder: 3\ndraft: true\n---\n# About\n");
+
+        assert_eq!(
+            meta,
+            PageMeta {
+                title: Some("About".to_owned()),
+                order: 3,
+                draft: true,
+                hidden: false,
+            }
+        );
+        assert_eq!(body, "# About\n");
+    }
+
+    #[test]
+    fn parses_crlf_front_matter() {
+        let (meta, body) = parse("---\r\ntitle: About\r\nhidden: true\r\n---\r\n# About\r\n");
+
+        assert_eq!(meta.title.as_deref(), Some("About"));
+        assert!(meta.hidden);
+        assert_eq!(body, "# About\r\n");
+    }
+
+    #[test]
+    fn keeps_markdown_without_front_matter() {
+        let markdown = "# About\n---\n";
+
+        assert_eq!(parse(markdown), (PageMeta::default(), markdown));
+    }
+
+    #[test]
+    fn keeps_unterminated_front_matter() {
+        let markdown = "---\ntitle: About\n# About\n";
+
+        assert_eq!(parse(markdown), (PageMeta::default(), markdown));
+    }
+
+    #[test]
+    fn ignores_invalid_order() {
+        let (meta, _) = parse("---\ntitle: About\norder: first\n---\n");
+
+        assert_eq!(meta.title.as_deref(), Some("About"));
+        assert_eq!(meta.order, 0);
+    }
+
+    #[test]
+    fn ignores_unknown_keys() {
+        let (meta, body) = parse("---\nauthor: tye\ntitle: About\n---\nBody");
+
+        assert_eq!(meta.title.as_deref(), Some("About"));
+        assert_eq!(body, "Body");
+    }
+}
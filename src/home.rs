@@ -0,0 +1,70 @@
+use std::sync::mpsc;
+
+use crate::{
+    fetch::{self, FetchState},
+    front_matter, js_imports,
+};
+
+/// Storage key for the cached remote home page markdown.
+pub const CACHE_KEY: &str = "tye_home-HomeCache";
+
+/// How long cached remote markdown is used before it is fetched again, in milliseconds.
+const CACHE_TTL: f64 = 60.0 * 60.0 * 1000.0;
+
+/// The maximum length of remote markdown, in bytes.
+const MAX_LEN: usize = 64 * 1024;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+/// Remote home page markdown, cached in storage.
+struct Cached {
+    /// The url the markdown was fetched from.
+    url: String,
+    /// When the markdown was fetched, in milliseconds since the unix epoch.
+    fetched: f64,
+    markdown: String,
+}
+
+/// Loads the cached markdown for the url, if it hasn't expired.
+pub fn load_cached(storage: &dyn eframe::Storage, url: &str) -> Option<String> {
+    let cached: Cached = eframe::get_value(storage, CACHE_KEY)?;
+
+    let fresh = cached.url == url && js_imports::now() - cached.fetched < CACHE_TTL;
+    fresh.then_some(cached.markdown)
+}
+
+/// Caches the markdown fetched from the url.
+pub fn store_cached(storage: &mut dyn eframe::Storage, url: &str, markdown: &str) {
+    let cached = Cached {
+        url: url.to_owned(),
+        fetched: js_imports::now(),
+        markdown: markdown.to_owned(),
+    };
+    eframe::set_value(storage, CACHE_KEY, &cached);
+}
+
+/// Fetches the home page markdown in the background, sending the progress through the sender.
+pub fn spawn_fetch(url: String, sender: mpsc::Sender<FetchState<String>>, ctx: egui::Context) {
+    log::debug!("Home page url: {url}");
+    fetch::spawn("home page", async move { fetch(&url).await }, sender, ctx);
+}
+
+/// Fetches the markdown from the url.
+async fn fetch(url: &str) -> Result<String, reqwest::Error> {
+    let markdown = reqwest::get(url).await?.error_for_status()?.text().await?;
+    Ok(sanitize(markdown))
+}
+
+/// Limits the size of the markdown & removes any front matter.
+fn sanitize(mut markdown: String) -> String {
+    if markdown.len() > MAX_LEN {
+        log::warn!("Home page is {} bytes, truncating it.", markdown.len());
+
+        let mut end = MAX_LEN;
+        while !markdown.is_char_boundary(end) {
+            end -= 1;
+        }
+        markdown.truncate(end);
+    }
+
+    front_matter::parse(&markdown).1.to_owned()
+}
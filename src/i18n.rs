@@ -0,0 +1,109 @@
+//! Translations of the UI text, from the tables in `assets/lang`.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        OnceLock,
+    },
+};
+
+use crate::js_imports;
+
+/// The language the UI is currently displayed in, as a [`Lang`] discriminant.
+static CURRENT: AtomicU8 = AtomicU8::new(Lang::English as u8);
+
+/// A language the UI can be displayed in.
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum Lang {
+    #[default]
+    English,
+    German,
+}
+
+impl Lang {
+    /// Every language, in the order they are listed.
+    pub const ALL: [Lang; 2] = [Lang::English, Lang::German];
+
+    /// Gets the language for the language tag, such as "de-DE".
+    ///
+    /// Returns `None` for languages without translations.
+    pub fn from_tag(tag: &str) -> Option<Lang> {
+        let primary = tag.split('-').next()?.to_lowercase();
+        match primary.as_str() {
+            "en" => Some(Lang::English),
+            "de" => Some(Lang::German),
+            _ => None,
+        }
+    }
+
+    /// Gets the preferred language of the browser, or English if it isn't translated.
+    pub fn browser() -> Lang {
+        let tag = js_imports::language();
+        Lang::from_tag(&tag).unwrap_or_else(|| {
+            log::debug!("No translation for the browser language: {tag}");
+            Lang::default()
+        })
+    }
+
+    /// Gets the translations for this language.
+    fn table(self) -> &'static HashMap<String, String> {
+        static ENGLISH: OnceLock<HashMap<String, String>> = OnceLock::new();
+        static GERMAN: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+        let (table, json) = match self {
+            Lang::English => (&ENGLISH, include_str!("../assets/lang/en.json")),
+            Lang::German => (&GERMAN, include_str!("../assets/lang/de.json")),
+        };
+
+        table.get_or_init(|| {
+            serde_json::from_str(json).unwrap_or_else(|e| {
+                log::error!("Unable to parse the {self} translations: {e}");
+                HashMap::new()
+            })
+        })
+    }
+}
+
+impl std::fmt::Display for Lang {
+    /// Displays the name of the language, in that language.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Lang::English => "English",
+            Lang::German => "Deutsch",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Gets the language the UI is currently displayed in.
+pub fn lang() -> Lang {
+    match CURRENT.load(Ordering::Relaxed) {
+        value if value == Lang::German as u8 => Lang::German,
+        _ => Lang::English,
+    }
+}
+
+/// Sets the language the UI is displayed in.
+pub fn set_lang(lang: Lang) {
+    log::debug!("Language set to {lang}.");
+    CURRENT.store(lang as u8, Ordering::Relaxed);
+}
+
+/// Gets the translation of the key in the current language, if there is one.
+pub fn get(key: &str) -> Option<String> {
+    lang().table().get(key).cloned()
+}
+
+/// Gets the text for the key in the current language.
+///
+/// Falls back to the English text if there is no translation, or the key itself if there is no text.
+pub fn tr(key: &str) -> String {
+    get(key)
+        .or_else(|| Lang::English.table().get(key).cloned())
+        .unwrap_or_else(|| {
+            log::warn!("Missing text for: {key}");
+            key.to_owned()
+        })
+}
@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use crate::app::Page;
+
+/// The idle timeout used when it's first enabled, in seconds.
+const DEFAULT_TIMEOUT: f64 = 300.0;
+
+/// The idle timeouts that can be chosen, in seconds.
+const TIMEOUT_RANGE: std::ops::RangeInclusive<f64> = 5.0..=3600.0;
+
+#[derive(serde::Deserialize, serde::Serialize, Debug)]
+#[serde(default)]
+/// Dims the app after a period without any input.
+pub struct Idle {
+    /// Seconds without input before the app idles, or `None` if disabled.
+    #[serde(deserialize_with = "deserialize_timeout")]
+    pub timeout: Option<f64>,
+    /// The page to return to when the app idles.
+    pub page: Page,
+
+    #[serde(skip)]
+    /// When input was last received, in seconds since the app started.
+    last_input: f64,
+    #[serde(skip)]
+    /// Whether the app is currently idle.
+    idle: bool,
+    #[serde(skip)]
+    /// Whether the overlay is shown this frame.
+    overlay: bool,
+}
+
+impl Default for Idle {
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            page: Page::Home,
+            last_input: 0.0,
+            idle: false,
+            overlay: false,
+        }
+    }
+}
+
+impl Idle {
+    /// Updates the idle state from this frame's input.
+    ///
+    /// Returns true on the frame that the app becomes idle.
+    pub fn update(&mut self, ctx: &egui::Context) -> bool {
+        let Some(timeout) = self.timeout else {
+            self.idle = false;
+            self.overlay = false;
+            return false;
+        };
+
+        let now = ctx.input(|i| i.time);
+
+        // The overlay stays for the frame that resumes,
+        // so the input isn't passed to the widgets underneath.
+        self.overlay = self.idle;
+
+        if ctx.input(|i| !i.events.is_empty()) {
+            self.last_input = now;
+            self.idle = false;
+        }
+
+        if self.idle {
+            return false;
+        }
+
+        let remaining = self.last_input + timeout - now;
+        if remaining > 0.0 {
+            // Checks again once the timeout would be reached.
+            ctx.request_repaint_after(Duration::from_secs_f64(remaining));
+            return false;
+        }
+
+        log::debug!("Idle timeout reached.");
+        self.idle = true;
+        self.overlay = true;
+        true
+    }
+
+    /// Displays the dimmed "tap to resume" overlay while idle.
+    pub fn show_overlay(&self, ctx: &egui::Context) {
+        if !self.overlay {
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("idle_overlay"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(egui::Pos2::ZERO)
+            .show(ctx, |ui| {
+                let screen = ctx.screen_rect();
+                ui.allocate_rect(screen, egui::Sense::click());
+
+                ui.painter()
+                    .rect_filled(screen, 0.0, egui::Color32::from_black_alpha(200));
+                ui.painter().text(
+                    screen.center(),
+                    egui::Align2::CENTER_CENTER,
+                    "Tap to resume",
+                    egui::FontId::proportional(24.0),
+                    egui::Color32::WHITE,
+                );
+            });
+    }
+
+    /// Displays the options for configuring the idle timeout.
+    pub fn options_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let mut enabled = self.timeout.is_some();
+            if ui.checkbox(&mut enabled, "Idle Timeout").changed() {
+                self.timeout = enabled.then_some(DEFAULT_TIMEOUT);
+                self.last_input = ui.input(|i| i.time);
+            }
+
+            if let Some(timeout) = &mut self.timeout {
+                ui.add(
+                    egui::DragValue::new(timeout)
+                        .range(TIMEOUT_RANGE)
+                        .suffix("s"),
+                );
+            }
+        });
+
+        egui::ComboBox::from_label("Idle Page")
+            .selected_text(self.page.to_string())
+            .show_ui(ui, |ui| {
+                for page in Page::all().iter().copied() {
+                    if page == Page::NotFound {
+                        continue;
+                    }
+                    ui.selectable_value(&mut self.page, page, page.to_string());
+                }
+            });
+    }
+}
+
+/// Reads a stored timeout, clamped to [`TIMEOUT_RANGE`].
+///
+/// Stored or imported state may be corrupt or hand-edited, & a huge timeout can't be waited for.
+fn deserialize_timeout<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let timeout: Option<f64> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(timeout.map(|timeout| match timeout.is_nan() {
+        true => DEFAULT_TIMEOUT,
+        false => timeout.clamp(*TIMEOUT_RANGE.start(), *TIMEOUT_RANGE.end()),
+    }))
+}
@@ -1,6 +1,165 @@
+use std::cell::Cell;
+
+#[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::*;
+
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen(module = "/assets/snippets.js")]
 extern "C" {
-    pub fn is_mobile() -> bool;
+    #[wasm_bindgen(js_name = is_mobile)]
+    fn is_mobile_uncached() -> bool;
+    pub fn reload();
+    pub fn query_param(name: &str) -> Option<String>;
+    pub fn now() -> f64;
+    pub fn format_time(time: f64) -> String;
+    pub fn get_hash() -> String;
+    pub fn set_hash(hash: &str);
+    pub fn inner_width() -> f64;
+    pub fn prefers_reduced_motion() -> bool;
+    pub fn prefers_dark() -> bool;
+    pub fn is_online() -> bool;
+    pub fn user_agent() -> String;
+    pub fn platform() -> String;
+    pub fn language() -> String;
+    pub fn download(filename: &str, contents: &str);
+    pub fn href() -> String;
+    pub fn can_share() -> bool;
+    pub fn hide_splash();
+    pub fn open_url(url: &str);
+    pub fn show_error(message: &str);
+    #[wasm_bindgen(catch)]
+    pub async fn copy_to_clipboard(text: &str) -> Result<JsValue, JsValue>;
+    #[wasm_bindgen(catch)]
+    pub async fn pick_file() -> Result<JsValue, JsValue>;
+    #[wasm_bindgen(catch)]
+    pub async fn share(title: &str, url: &str) -> Result<JsValue, JsValue>;
+}
+
+/// Stand-ins for the browser functions, so the app can be built & tested natively.
+///
+/// They behave like a desktop browser that doesn't support any of the optional features.
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use wasm_bindgen::JsValue;
+
+    pub(super) fn is_mobile_uncached() -> bool {
+        false
+    }
+
+    pub fn reload() {
+        log::warn!("Reloading is unsupported natively.");
+    }
+
+    pub fn query_param(_name: &str) -> Option<String> {
+        None
+    }
+
+    pub fn now() -> f64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0.0, |elapsed| elapsed.as_secs_f64() * 1000.0)
+    }
+
+    /// Formats the time in UTC, as the local timezone isn't known.
+    pub fn format_time(time: f64) -> String {
+        let seconds = (time / 1000.0) as u64 % 86_400;
+        format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+
+    pub fn get_hash() -> String {
+        String::new()
+    }
+
+    pub fn set_hash(_hash: &str) {}
+
+    pub fn inner_width() -> f64 {
+        1280.0
+    }
+
+    pub fn prefers_reduced_motion() -> bool {
+        false
+    }
+
+    pub fn prefers_dark() -> bool {
+        true
+    }
+
+    pub fn is_online() -> bool {
+        true
+    }
+
+    pub fn user_agent() -> String {
+        format!("native ({})", std::env::consts::ARCH)
+    }
+
+    pub fn platform() -> String {
+        std::env::consts::OS.to_owned()
+    }
+
+    pub fn language() -> String {
+        "en".to_owned()
+    }
+
+    pub fn download(filename: &str, _contents: &str) {
+        log::warn!("Unable to download {filename}, downloads are unsupported natively.");
+    }
+
+    pub fn href() -> String {
+        String::new()
+    }
+
+    pub fn can_share() -> bool {
+        false
+    }
+
+    pub fn hide_splash() {}
+
+    pub fn open_url(url: &str) {
+        log::warn!("Unable to open {url}, opening links is unsupported natively.");
+    }
+
+    /// Errors are already logged to stderr, so there is nothing more to show.
+    pub fn show_error(_message: &str) {}
+
+    pub async fn copy_to_clipboard(_text: &str) -> Result<JsValue, &'static str> {
+        Err("the clipboard is unsupported natively")
+    }
+
+    pub async fn pick_file() -> Result<JsValue, &'static str> {
+        Err("picking files is unsupported natively")
+    }
+
+    pub async fn share(_title: &str, _url: &str) -> Result<JsValue, &'static str> {
+        Err("sharing is unsupported natively")
+    }
+}
+
+thread_local! {
+    /// Whether the user is on a mobile device, once it has been checked.
+    static IS_MOBILE: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// Returns whether the user is on a mobile device.
+///
+/// This doesn't change during a session, so it is only checked once.
+pub fn is_mobile() -> bool {
+    IS_MOBILE.with(|is_mobile| match is_mobile.get() {
+        Some(cached) => cached,
+        None => refresh_is_mobile(),
+    })
+}
+
+/// Checks whether the user is on a mobile device again, updating the cached value.
+pub fn refresh_is_mobile() -> bool {
+    let checked = is_mobile_uncached();
+    IS_MOBILE.with(|is_mobile| is_mobile.set(Some(checked)));
+    checked
 }
@@ -1,8 +1,21 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 pub mod app;
+mod compress;
+pub mod console;
+pub mod fetch;
+pub mod front_matter;
+pub mod home;
+pub mod i18n;
+pub mod idle;
 pub mod js_imports;
 mod logger;
+pub mod palette;
+pub mod perf;
+pub mod profile;
+pub mod throttle;
+pub mod tint;
+pub mod toast;
 
 pub use app::MyApp;
-pub use logger::{Logger, Transmitted as LogType};
+pub use logger::{install_panic_hook, Logger, Sink as LogSink, Transmitted as LogType};
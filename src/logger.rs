@@ -1,65 +1,769 @@
-use std::sync::mpsc;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc, Arc, Mutex, OnceLock,
+    },
+};
 
-use eframe::WebLogger;
+use crate::js_imports;
 
-pub type Transmitted = (log::Level, String);
+/// A log sent to the application.
+#[derive(Debug, Clone)]
+pub struct Transmitted {
+    pub level: log::Level,
+    /// Where the log came from, which is the module path unless set otherwise.
+    pub target: String,
+    /// The module the log was made in, if known.
+    pub module_path: Option<String>,
+    pub message: String,
+    /// When it was logged, in milliseconds since the unix epoch.
+    pub time: f64,
+    /// How many times it was received in a row, once repeats are merged by the application.
+    pub count: usize,
+}
+
+/// How logs are formatted for display.
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Only the level & message.
+    Plain,
+    /// The time, level, & message.
+    #[default]
+    WithTimestamp,
+    /// The time, level, target, & message.
+    WithTarget,
+}
+
+impl LogFormat {
+    /// All the formats, in order of verbosity.
+    pub const ALL: [LogFormat; 3] = [
+        LogFormat::Plain,
+        LogFormat::WithTimestamp,
+        LogFormat::WithTarget,
+    ];
+}
+
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogFormat::Plain => write!(f, "Plain"),
+            LogFormat::WithTimestamp => write!(f, "With Timestamp"),
+            LogFormat::WithTarget => write!(f, "With Target"),
+        }
+    }
+}
+
+impl Transmitted {
+    /// Formats the log as a single line.
+    pub fn format(&self, format: LogFormat) -> String {
+        let formatted = match format {
+            LogFormat::Plain => format!("{}: {}", self.level, self.message),
+            LogFormat::WithTimestamp => format!(
+                "{} {}: {}",
+                format_time(self.time),
+                self.level,
+                self.message
+            ),
+            LogFormat::WithTarget => format!(
+                "{} {} [{}]: {}",
+                format_time(self.time),
+                self.level,
+                self.target,
+                self.message
+            ),
+        };
+
+        match self.count {
+            0 | 1 => formatted,
+            count => format!("{formatted} (×{count})"),
+        }
+    }
+}
+
+/// Gets the colour logs of the level are displayed with, so their severity stands out.
+pub fn level_colour(visuals: &egui::Visuals, level: log::Level) -> egui::Color32 {
+    match level {
+        log::Level::Error => visuals.error_fg_color,
+        log::Level::Warn => visuals.warn_fg_color,
+        log::Level::Info => visuals.text_color(),
+        log::Level::Debug | log::Level::Trace => visuals.weak_text_color(),
+    }
+}
+
+/// The default maximum length of messages sent to the application, in bytes.
+pub const DEFAULT_MAX_MESSAGE_LEN: usize = 16 * 1024;
+
+/// How many times a message is logged in a row before the repeats are collapsed.
+const REPEAT_THRESHOLD: usize = 3;
+
+/// How long repeats of a message are collapsed for, in milliseconds.
+pub const REPEAT_WINDOW: f64 = 1000.0;
+
+/// The most recently logged message, & how many times it has been logged in a row.
+struct Repeated {
+    level: log::Level,
+    target: String,
+    message: String,
+    /// When the message was first logged, in milliseconds since the unix epoch.
+    since: f64,
+    count: usize,
+}
+
+/// Where logs are written to, alongside the application.
+pub trait Sink: Send + Sync {
+    /// Writes the record, with the full message.
+    fn log(&self, record: &log::Record<'_>);
+
+    /// Flushes any buffered records.
+    fn flush(&self) {}
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Sink for eframe::WebLogger {
+    fn log(&self, record: &log::Record<'_>) {
+        log::Log::log(self, record);
+    }
+
+    fn flush(&self) {
+        log::Log::flush(self);
+    }
+}
+
+/// Writes logs to stderr, for native builds.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct StderrSink;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Sink for StderrSink {
+    fn log(&self, record: &log::Record<'_>) {
+        eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+    }
+}
+
+/// Gets the sink for the platform being built for.
+#[cfg(target_arch = "wasm32")]
+fn default_sink() -> Box<dyn Sink> {
+    // The sink filters nothing, as the logger has already filtered the logs.
+    Box::new(eframe::WebLogger::new(log::LevelFilter::Trace))
+}
+
+/// Gets the sink for the platform being built for.
+#[cfg(not(target_arch = "wasm32"))]
+fn default_sink() -> Box<dyn Sink> {
+    Box::new(StderrSink)
+}
+
+/// Gets the current time, in milliseconds since the unix epoch.
+pub fn now() -> f64 {
+    js_imports::now()
+}
+
+/// Formats a time, in milliseconds since the unix epoch, as a "HH:MM:SS" string.
+///
+/// This is local time on the web, & UTC natively.
+fn format_time(time: f64) -> String {
+    js_imports::format_time(time)
+}
+
+/// Installs a panic hook that logs panics, so they reach the application's logs & the console.
+///
+/// The panic is also shown over the app, as the app stops rendering once it has panicked.
+/// The previous panic hook is still called afterwards.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        log::error!(target: "panic", "The app panicked: {info}");
+        js_imports::show_error(&info.to_string());
+        previous(info);
+    }));
+}
+
+/// The filter of the installed logger, so it can be changed at runtime.
+static INSTALLED_FILTER: OnceLock<FilterHandle> = OnceLock::new();
+
+/// The installed logger, so collapsed repeats can be logged without a new message.
+static INSTALLED_LOGGER: OnceLock<&'static Logger> = OnceLock::new();
+
+/// Gets the filter of the installed [`Logger`], if one has been installed.
+pub fn installed_filter() -> Option<&'static FilterHandle> {
+    INSTALLED_FILTER.get()
+}
+
+/// Repaints the context whenever the installed [`Logger`] sends a log to the application.
+pub fn repaint_on_log(ctx: &egui::Context) {
+    if let Some(logger) = INSTALLED_LOGGER.get() {
+        logger.repaint_on_log(ctx);
+    }
+}
+
+/// Logs how many times a message was repeated, if its collapse window has expired.
+///
+/// Otherwise the repeats are only logged once a different message is logged.
+pub fn flush_expired_repeats() {
+    if let Some(logger) = INSTALLED_LOGGER.get() {
+        logger.flush_repeats(Some(now()));
+    }
+}
+
+/// Changes the global filter of a [`Logger`] while it is running.
+#[derive(Debug, Clone)]
+pub struct FilterHandle {
+    /// The global filter, stored as its discriminant.
+    filter: Arc<AtomicUsize>,
+    /// The most verbose module filter, which the max level can't go below.
+    module_max: log::LevelFilter,
+}
+
+impl FilterHandle {
+    /// Creates a handle with the given global filter.
+    fn new(filter: log::LevelFilter) -> Self {
+        Self {
+            filter: Arc::new(AtomicUsize::new(filter as usize)),
+            module_max: log::LevelFilter::Off,
+        }
+    }
+
+    /// Gets the global filter.
+    pub fn level(&self) -> log::LevelFilter {
+        let value = self.filter.load(Ordering::Relaxed);
+        log::LevelFilter::iter()
+            .find(|filter| *filter as usize == value)
+            .unwrap_or(log::LevelFilter::Trace)
+    }
+
+    /// Sets the global filter, which applies to logs without a module filter.
+    pub fn set_level(&self, filter: log::LevelFilter) {
+        self.filter.store(filter as usize, Ordering::Relaxed);
+        // Module filters may be more verbose than the global filter.
+        log::set_max_level(filter.max(self.module_max));
+    }
+}
 
 pub struct Logger {
-    filter: log::LevelFilter,
-    web_logger: WebLogger,
+    /// The global filter, which can be changed while running.
+    filter: FilterHandle,
+    /// Filters for specific modules, which take priority over the global filter.
+    module_filters: HashMap<String, log::LevelFilter>,
+    /// Where logs are written, besides the application.
+    sink: Box<dyn Sink>,
+    /// Messages longer than this are truncated before being sent to the application.
+    max_message_len: usize,
+    /// Whether repeated messages are collapsed into a single log.
+    collapse_repeats: bool,
+    repeated: Mutex<Option<Repeated>>,
+    /// Wakes the application when a log is sent to it.
+    repaint: OnceLock<Repaint>,
 
     log_sender: mpsc::Sender<Transmitted>,
 }
 
+/// Requests a repaint of the application, so logs sent outside of a pass are shown.
+///
+/// A throttled application otherwise wouldn't receive logs from background tasks until its next pass.
+struct Repaint {
+    ctx: egui::Context,
+    /// Whether egui is running a pass, which already receives its own logs.
+    in_pass: Arc<AtomicBool>,
+}
+
+impl Repaint {
+    /// Starts tracking the passes of the context.
+    fn new(ctx: &egui::Context) -> Self {
+        let in_pass = Arc::new(AtomicBool::new(false));
+        let begin = in_pass.clone();
+        ctx.on_begin_pass(
+            "logger",
+            Arc::new(move |_| begin.store(true, Ordering::Relaxed)),
+        );
+        let end = in_pass.clone();
+        ctx.on_end_pass(
+            "logger",
+            Arc::new(move |_| end.store(false, Ordering::Relaxed)),
+        );
+
+        Self {
+            ctx: ctx.clone(),
+            in_pass,
+        }
+    }
+
+    /// Requests a repaint for a log from the target.
+    fn request(&self, target: &str) {
+        // egui may log while its context is locked, so requesting a repaint would deadlock.
+        let from_egui = ["egui", "epaint"]
+            .iter()
+            .any(|krate| target.starts_with(krate));
+        if !from_egui && !self.in_pass.load(Ordering::Relaxed) {
+            self.ctx.request_repaint();
+        }
+    }
+}
+
 impl Logger {
     /// Install a new `Logger`, piping all [`log`] events to the web console
-    /// and to my application
+    /// (or stderr natively) and to my application
     pub fn init(
         filter: log::LevelFilter,
     ) -> Result<mpsc::Receiver<Transmitted>, log::SetLoggerError> {
         let (tx, rx) = mpsc::channel();
+        Self::new(filter, tx).install()?;
+        Ok(rx)
+    }
 
-        log::set_max_level(filter);
-        log::set_boxed_logger(Box::new(Self::new(filter, tx)))?;
+    /// Installs this `Logger`, piping all [`log`] events to the web console
+    /// and to my application
+    pub fn install(mut self) -> Result<(), log::SetLoggerError> {
+        self.filter.module_max = self
+            .module_filters
+            .values()
+            .copied()
+            .fold(log::LevelFilter::Off, Ord::max);
+        let filter = self.filter.clone();
 
-        Ok(rx)
+        let logger: &'static Logger = Box::leak(Box::new(self));
+        log::set_logger(logger)?;
+        filter.set_level(filter.level());
+        let _ = INSTALLED_FILTER.set(filter);
+        let _ = INSTALLED_LOGGER.set(logger);
+        Ok(())
     }
 
     /// Creates a new [`Logger`] with the given filter, but don't install it.
     pub fn new(filter: log::LevelFilter, log_sender: mpsc::Sender<Transmitted>) -> Self {
         Self {
-            filter,
-            web_logger: eframe::WebLogger::new(filter),
+            filter: FilterHandle::new(filter),
+            module_filters: HashMap::new(),
+            sink: default_sink(),
+            max_message_len: DEFAULT_MAX_MESSAGE_LEN,
+            collapse_repeats: true,
+            repeated: Mutex::new(None),
+            repaint: OnceLock::new(),
             log_sender,
         }
     }
+
+    /// Sets where logs are written, besides the application.
+    pub fn with_sink(mut self, sink: impl Sink + 'static) -> Self {
+        self.sink = Box::new(sink);
+        self
+    }
+
+    /// Sets the maximum length of messages sent to the application, in bytes.
+    pub fn with_max_message_len(mut self, max_message_len: usize) -> Self {
+        self.max_message_len = max_message_len;
+        self
+    }
+
+    /// Sets whether a message logged many times in a row is collapsed into a single log.
+    pub fn with_collapse_repeats(mut self, collapse_repeats: bool) -> Self {
+        self.collapse_repeats = collapse_repeats;
+        self
+    }
+
+    /// Sets the filter for logs from the given target, & any modules within it.
+    pub fn with_module_filter(
+        mut self,
+        target: impl Into<String>,
+        level: log::LevelFilter,
+    ) -> Self {
+        self.module_filters.insert(target.into(), level);
+        self
+    }
+
+    /// Repaints the context whenever a log is sent to the application, so it is shown promptly.
+    ///
+    /// Only the first context is repainted.
+    pub fn repaint_on_log(&self, ctx: &egui::Context) {
+        let _ = self.repaint.get_or_init(|| Repaint::new(ctx));
+    }
+
+    /// Gets the filter for the given target, using the most specific matching module filter.
+    fn filter_for(&self, target: &str) -> log::LevelFilter {
+        self.module_filters
+            .iter()
+            .filter(|(module, _)| {
+                target == module.as_str()
+                    || target
+                        .strip_prefix(module.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(module, _)| module.len())
+            .map_or_else(|| self.filter.level(), |(_, level)| *level)
+    }
 }
 
-impl log::Log for Logger {
-    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
-        metadata.level() <= self.filter
+/// Truncates the message to at most `max_len` bytes, noting how many bytes were removed.
+fn truncate(mut message: String, max_len: usize) -> String {
+    if message.len() <= max_len {
+        return message;
     }
 
-    fn log(&self, record: &log::Record<'_>) {
-        // Logs to js console.
-        self.web_logger.log(record);
+    let mut end = max_len;
+    while !message.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    let removed = message.len() - end;
+    message.truncate(end);
+    message.push_str(&format!("…[truncated {removed} bytes]"));
+    message
+}
+
+impl Logger {
+    /// Tracks the message, returning true if it is a repeat that shouldn't be logged.
+    ///
+    /// Once a collapsed message stops repeating, the number of repeats is logged.
+    fn collapse(&self, record: &log::Record<'_>, message: &str, now: f64) -> bool {
+        self.flush_repeats(Some(now));
+
+        let Ok(mut repeated) = self.repeated.lock() else {
+            return false;
+        };
+
+        if let Some(last) = repeated.as_mut() {
+            if last.level == record.level() && last.message == message {
+                last.count += 1;
+                return last.count > REPEAT_THRESHOLD;
+            }
+        }
+
+        let previous = repeated.replace(Repeated {
+            level: record.level(),
+            target: record.target().to_owned(),
+            message: message.to_owned(),
+            since: now,
+            count: 1,
+        });
+        drop(repeated);
+
+        if let Some(previous) = previous {
+            self.emit_repeats(previous);
+        }
+        false
+    }
+
+    /// Stops tracking the last message, logging how many times it was repeated.
+    ///
+    /// Given the current time, this only happens once the collapse window has expired.
+    fn flush_repeats(&self, now: Option<f64>) {
+        let Ok(mut repeated) = self.repeated.lock() else {
+            return;
+        };
+
+        let expired = match (repeated.as_ref(), now) {
+            (None, _) => false,
+            (Some(last), Some(now)) => now - last.since >= REPEAT_WINDOW,
+            (Some(_), None) => true,
+        };
+        let previous = if expired { repeated.take() } else { None };
+        drop(repeated);
+
+        if let Some(previous) = previous {
+            self.emit_repeats(previous);
+        }
+    }
+
+    /// Logs how many times the message was hidden, if it was repeated enough to be collapsed.
+    fn emit_repeats(&self, previous: Repeated) {
+        if previous.count <= REPEAT_THRESHOLD {
+            return;
+        }
+
+        let hidden = previous.count - REPEAT_THRESHOLD;
+        self.emit(
+            &log::Record::builder()
+                .level(previous.level)
+                .target(&previous.target)
+                .args(format_args!(
+                    "(previous message repeated {hidden} more times)"
+                ))
+                .build(),
+        );
+    }
+
+    /// Logs the record to the sink & the application.
+    fn emit(&self, record: &log::Record<'_>) {
+        // Logs to the sink, with the full message.
+        self.sink.log(record);
 
         // Logs to application.
-        let send_result = self
-            .log_sender
-            .send((record.level(), record.args().to_string()));
+        let message = truncate(record.args().to_string(), self.max_message_len);
+        let send_result = self.log_sender.send(Transmitted {
+            level: record.level(),
+            target: record.target().to_owned(),
+            module_path: record.module_path().map(str::to_owned),
+            message,
+            time: now(),
+            count: 1,
+        });
+
+        if send_result.is_ok() {
+            if let Some(repaint) = self.repaint.get() {
+                repaint.request(record.target());
+            }
+        }
 
         // Inform of applocation logging failure.
-        if let Err(_) = send_result {
+        if send_result.is_err() {
             let warn_log = log::Record::builder()
                 .level(log::Level::Warn)
                 .args(format_args!("Unable to send previous log to application."))
                 .build();
-            self.web_logger.log(&warn_log);
+            self.sink.log(&warn_log);
+        }
+    }
+}
+
+impl log::Log for Logger {
+    fn enabled(&self, metadata: &log::Metadata<'_>) -> bool {
+        metadata.level() <= self.filter_for(metadata.target())
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        if self.collapse_repeats && self.collapse(record, &record.args().to_string(), now()) {
+            return;
         }
+
+        self.emit(record);
     }
 
     fn flush(&self) {
-        self.web_logger.flush();
+        self.flush_repeats(None);
+        self.sink.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Collects the messages it is given, so tests can check what reached the sink.
+    #[derive(Clone, Default)]
+    struct CollectingSink(Arc<Mutex<Vec<String>>>);
+
+    impl Sink for CollectingSink {
+        fn log(&self, record: &log::Record<'_>) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    /// Logs a message through the logger, without installing it globally.
+    fn log(logger: &Logger, level: log::Level, message: &str) {
+        log::Log::log(
+            logger,
+            &log::Record::builder()
+                .level(level)
+                .target("tye_home::test")
+                .args(format_args!("{message}"))
+                .build(),
+        );
+    }
+
+    #[test]
+    fn logs_reach_sink_and_application() {
+        let (tx, rx) = mpsc::channel();
+        let sink = CollectingSink::default();
+        let logger = Logger::new(log::LevelFilter::Info, tx).with_sink(sink.clone());
+
+        log(&logger, log::Level::Info, "shown");
+        log(&logger, log::Level::Debug, "filtered");
+
+        assert_eq!(*sink.0.lock().unwrap(), ["shown"]);
+        let received: Vec<Transmitted> = rx.try_iter().collect();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].message, "shown");
+        assert_eq!(received[0].target, "tye_home::test");
+        assert!(received[0].time > 0.0);
+    }
+
+    #[test]
+    fn giant_messages_are_truncated() {
+        let (tx, rx) = mpsc::channel();
+        let sink = CollectingSink::default();
+        let logger = Logger::new(log::LevelFilter::Trace, tx)
+            .with_sink(sink.clone())
+            .with_max_message_len(16);
+
+        log(&logger, log::Level::Info, &"a".repeat(1000));
+
+        let received: Vec<Transmitted> = rx.try_iter().collect();
+        assert_eq!(
+            received[0].message,
+            format!("{}…[truncated 984 bytes]", "a".repeat(16))
+        );
+        // The sink still gets the full message.
+        assert_eq!(sink.0.lock().unwrap()[0].len(), 1000);
+    }
+
+    /// Logs a message through the logger at the given time, so repeats can be timed.
+    fn log_at(logger: &Logger, message: &str, now: f64) {
+        let log = |record: &log::Record<'_>| {
+            if !logger.collapse(record, message, now) {
+                logger.emit(record);
+            }
+        };
+        log(&log::Record::builder()
+            .level(log::Level::Info)
+            .target("tye_home::test")
+            .args(format_args!("{message}"))
+            .build());
+    }
+
+    /// Gets the messages that reached the application.
+    fn received(rx: &mpsc::Receiver<Transmitted>) -> Vec<String> {
+        rx.try_iter().map(|log| log.message).collect()
+    }
+
+    #[test]
+    fn repeats_are_collapsed_and_counted() {
+        let (tx, rx) = mpsc::channel();
+        let logger = Logger::new(log::LevelFilter::Trace, tx);
+
+        for time in 0..5 {
+            log_at(&logger, "spam", time as f64);
+        }
+        log_at(&logger, "other", 5.0);
+
+        assert_eq!(
+            received(&rx),
+            [
+                "spam",
+                "spam",
+                "spam",
+                "(previous message repeated 2 more times)",
+                "other"
+            ]
+        );
+    }
+
+    #[test]
+    fn repeats_are_counted_once_window_expires() {
+        let (tx, rx) = mpsc::channel();
+        let logger = Logger::new(log::LevelFilter::Trace, tx);
+
+        for time in 0..5 {
+            log_at(&logger, "spam", time as f64);
+        }
+        assert_eq!(received(&rx), ["spam", "spam", "spam"]);
+
+        logger.flush_repeats(Some(REPEAT_WINDOW / 2.0));
+        assert!(received(&rx).is_empty());
+
+        logger.flush_repeats(Some(REPEAT_WINDOW));
+        assert_eq!(received(&rx), ["(previous message repeated 2 more times)"]);
+
+        // The message is shown again once it has been counted.
+        log_at(&logger, "spam", REPEAT_WINDOW + 1.0);
+        assert_eq!(received(&rx), ["spam"]);
+    }
+
+    #[test]
+    fn flush_counts_pending_repeats() {
+        let (tx, rx) = mpsc::channel();
+        let logger = Logger::new(log::LevelFilter::Trace, tx);
+
+        // Nothing is pending.
+        log::Log::flush(&logger);
+        assert!(received(&rx).is_empty());
+
+        // Too few repeats to be collapsed.
+        log_at(&logger, "spam", 0.0);
+        log::Log::flush(&logger);
+        assert_eq!(received(&rx), ["spam"]);
+
+        for time in 1..6 {
+            log_at(&logger, "spam", time as f64);
+        }
+        received(&rx);
+        log::Log::flush(&logger);
+        assert_eq!(received(&rx), ["(previous message repeated 2 more times)"]);
+
+        // The repeats are only counted once.
+        log::Log::flush(&logger);
+        assert!(received(&rx).is_empty());
+    }
+
+    #[test]
+    fn module_filters_override_global_filter() {
+        let (tx, rx) = mpsc::channel();
+        let logger = Logger::new(log::LevelFilter::Info, tx)
+            .with_module_filter("tye_home", log::LevelFilter::Debug)
+            .with_module_filter("tye_home::test", log::LevelFilter::Warn)
+            .with_collapse_repeats(false);
+
+        // The most specific module filter applies.
+        assert_eq!(logger.filter_for("tye_home::app"), log::LevelFilter::Debug);
+        assert_eq!(logger.filter_for("tye_home::test"), log::LevelFilter::Warn);
+        assert_eq!(logger.filter_for("tye_homepage"), log::LevelFilter::Info);
+
+        log::Log::log(
+            &logger,
+            &log::Record::builder()
+                .level(log::Level::Debug)
+                .target("tye_home::app")
+                .args(format_args!("above global"))
+                .build(),
+        );
+        log(&logger, log::Level::Info, "below module");
+        log(&logger, log::Level::Warn, "at module");
+
+        assert_eq!(received(&rx), ["above global", "at module"]);
+    }
+
+    #[test]
+    fn sent_logs_request_repaint() {
+        let (tx, _rx) = mpsc::channel();
+        let logger = Logger::new(log::LevelFilter::Trace, tx);
+        let ctx = egui::Context::default();
+        logger.repaint_on_log(&ctx);
+
+        // The first passes request repaints of their own.
+        for _ in 0..3 {
+            let _ = ctx.run(Default::default(), |_| {});
+        }
+        assert!(!ctx.has_requested_repaint());
+
+        // Logs during a pass are received by that pass.
+        let _ = ctx.run(Default::default(), |_| log_at(&logger, "during", 0.0));
+        assert!(!ctx.has_requested_repaint());
+
+        log_at(&logger, "background", 0.0);
+        assert!(ctx.has_requested_repaint());
+    }
+
+    #[test]
+    fn truncates_at_char_boundary() {
+        // "é" is two bytes, so the limit falls in the middle of the third one.
+        let message = "éééé".to_owned();
+
+        assert_eq!(truncate(message.clone(), 5), "éé…[truncated 4 bytes]");
+        assert_eq!(truncate(message.clone(), 8), message);
+    }
+
+    #[test]
+    fn format_includes_time() {
+        let entry = Transmitted {
+            level: log::Level::Warn,
+            target: "tye_home::test".to_owned(),
+            module_path: None,
+            message: "message".to_owned(),
+            // 01:02:03 on the first day of the epoch.
+            time: 3_723_000.0,
+            count: 1,
+        };
+
+        assert_eq!(entry.format(LogFormat::Plain), "WARN: message");
+        assert_eq!(
+            entry.format(LogFormat::WithTarget),
+            "01:02:03 WARN [tye_home::test]: message"
+        );
+
+        let repeated = Transmitted { count: 3, ..entry };
+        assert_eq!(repeated.format(LogFormat::Plain), "WARN: message (×3)");
     }
 }
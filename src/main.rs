@@ -4,7 +4,12 @@
 // When compiling natively:
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result {
-    env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
+    // Log to stderr and to the debug menu:
+    let receiver = tye_home::Logger::init(log::LevelFilter::Debug).ok();
+    if receiver.is_none() {
+        log::warn!("Debug menu logger unavailable.");
+    }
+    tye_home::install_panic_hook();
 
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -20,7 +25,7 @@ fn main() -> eframe::Result {
     eframe::run_native(
         "eframe template",
         native_options,
-        Box::new(|cc| Ok(Box::new(tye_home::MyApp::new(cc)))),
+        Box::new(|cc| Ok(Box::new(tye_home::MyApp::new(cc, receiver)?))),
     )
 }
 
@@ -34,6 +39,7 @@ fn main() -> Result<(), tye_home::app::InitError> {
     if receiver.is_none() {
         log::warn!("Debug menu logger unavailable.");
     }
+    tye_home::install_panic_hook();
 
     let web_options = eframe::WebOptions::default();
 
@@ -57,24 +63,10 @@ fn main() -> Result<(), tye_home::app::InitError> {
             )
             .await;
 
-        // Remove the loading text and spinner.
-        // Otherwise it'll be permanently present.
-        let loading_text = web_sys::window()
-            .and_then(|w| w.document())
-            .and_then(|d| d.get_element_by_id("loading_text"));
-
-        if let Some(loading_text) = loading_text {
-            match start_result {
-                Ok(_) => {
-                    loading_text.remove();
-                }
-                Err(e) => {
-                    loading_text.set_inner_html(
-                        "<p> The app has crashed. See the developer console for details. </p>",
-                    );
-                    panic!("Failed to start eframe: {e:?}");
-                }
-            }
+        // The loading text and spinner are removed once the app has rendered.
+        if let Err(e) = start_result {
+            tye_home::js_imports::show_error(&format!("{e:?}"));
+            panic!("Failed to start eframe: {e:?}");
         }
     });
 
@@ -0,0 +1,124 @@
+use crate::app::Page;
+
+/// An action that can be run from the command palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    SwitchPage(Page),
+    ToggleDebug,
+    ToggleLayout,
+    ClearLogs,
+    ExportState,
+}
+
+impl Action {
+    /// Gets every action, in the order they are listed.
+    pub fn all() -> Vec<Action> {
+        Page::nav()
+            .into_iter()
+            .map(|(page, _)| Action::SwitchPage(page))
+            .chain([
+                Action::ToggleDebug,
+                Action::ToggleLayout,
+                Action::ClearLogs,
+                Action::ExportState,
+            ])
+            .collect()
+    }
+
+    /// Gets the label the action is listed with.
+    pub fn label(self) -> String {
+        match self {
+            Action::SwitchPage(page) => format!("Go to {page}"),
+            Action::ToggleDebug => "Toggle debug window".to_owned(),
+            Action::ToggleLayout => "Toggle layout".to_owned(),
+            Action::ClearLogs => "Clear logs".to_owned(),
+            Action::ExportState => "Export state".to_owned(),
+        }
+    }
+}
+
+/// Lists the actions matching a filter, so they can be run from the keyboard.
+#[derive(Debug, Default)]
+pub struct Palette {
+    /// Whether the palette is open.
+    pub open: bool,
+    /// Only actions containing this are listed.
+    filter: String,
+    /// The index of the highlighted action, within the listed actions.
+    selected: usize,
+}
+
+impl Palette {
+    /// Opens the palette if it is closed, or closes it if it is open.
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.filter.clear();
+        self.selected = 0;
+    }
+
+    /// Displays the palette, if it is open.
+    ///
+    /// Returns the action that was chosen, which closes the palette.
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<Action> {
+        if !self.open {
+            return None;
+        }
+
+        let filter = self.filter.to_lowercase();
+        let actions: Vec<Action> = Action::all()
+            .into_iter()
+            .filter(|action| action.label().to_lowercase().contains(&filter))
+            .collect();
+
+        let (up, down, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+            )
+        });
+
+        let last = actions.len().saturating_sub(1);
+        match (up, down) {
+            (true, _) => self.selected = self.selected.saturating_sub(1),
+            (_, true) => self.selected = (self.selected + 1).min(last),
+            _ => self.selected = self.selected.min(last),
+        }
+
+        let mut chosen = enter.then(|| actions.get(self.selected).copied()).flatten();
+
+        egui::Window::new("Command Palette")
+            .title_bar(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 40.0])
+            .show(ctx, |ui| {
+                let input = ui.add(
+                    egui::TextEdit::singleline(&mut self.filter)
+                        .hint_text("Type to filter actions.")
+                        .desired_width(300.0),
+                );
+                input.request_focus();
+                if input.changed() {
+                    self.selected = 0;
+                }
+
+                ui.separator();
+
+                if actions.is_empty() {
+                    ui.weak("No matching actions.");
+                }
+                for (index, action) in actions.iter().enumerate() {
+                    let label = ui.selectable_label(index == self.selected, action.label());
+                    if label.clicked() {
+                        chosen = Some(*action);
+                    }
+                }
+            });
+
+        if chosen.is_some() || escape {
+            self.toggle();
+        }
+        chosen
+    }
+}
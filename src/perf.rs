@@ -0,0 +1,58 @@
+use circular_queue::CircularQueue;
+
+/// How many frames the averages are taken over.
+const SAMPLES: usize = 60;
+
+/// Tracks recent frame times, to show how expensive repaints are.
+pub struct Perf {
+    /// The most recent frame times, in seconds.
+    frame_times: CircularQueue<f32>,
+}
+
+impl Default for Perf {
+    fn default() -> Self {
+        Self {
+            frame_times: CircularQueue::with_capacity(SAMPLES),
+        }
+    }
+}
+
+impl Perf {
+    /// Records this frame's time.
+    pub fn update(&mut self, ctx: &egui::Context) {
+        self.frame_times.push(ctx.input(|i| i.stable_dt));
+    }
+
+    /// The average frame time, in seconds.
+    pub fn average_frame_time(&self) -> f32 {
+        match self.frame_times.len() {
+            0 => 0.0,
+            len => self.frame_times.iter().sum::<f32>() / len as f32,
+        }
+    }
+
+    /// Displays the frame time & fps in the top right corner.
+    ///
+    /// Throttled repaints only happen on input, so the fps is low while throttled.
+    pub fn show(&self, ctx: &egui::Context, throttled: bool) {
+        let frame_time = self.average_frame_time();
+        let fps = if frame_time > 0.0 {
+            1.0 / frame_time
+        } else {
+            0.0
+        };
+
+        egui::Area::new(egui::Id::new("perf_overlay"))
+            .anchor(egui::Align2::RIGHT_TOP, [-8.0, 32.0])
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.monospace(format!("{:.2} ms", frame_time * 1000.0));
+                    ui.monospace(format!("{fps:.0} fps"));
+                    if throttled {
+                        ui.monospace("throttled");
+                    }
+                });
+            });
+    }
+}
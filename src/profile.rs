@@ -0,0 +1,172 @@
+use std::sync::mpsc;
+
+use crate::fetch::{self, FetchState};
+
+/// The lookup url for my Discord profile.
+const PROFILE_URL: &str = "https://discordlookup.mesalytic.moe/v1/user/1192519637448011827";
+
+/// My Discord profile information.
+#[derive(Debug, Clone)]
+pub struct Profile {
+    /// The display name of the profile.
+    pub name: String,
+    /// The url of the profile's avatar image, if it has one.
+    pub avatar: Option<String>,
+}
+
+impl Profile {
+    /// Placeholder profile data, for testing the UI without a network request.
+    pub fn sample() -> Self {
+        Self {
+            name: "tye".to_owned(),
+            avatar: None,
+        }
+    }
+}
+
+/// The fields used from the Discord lookup response.
+#[derive(serde::Deserialize, Debug)]
+struct DiscordUser {
+    raw: RawUser,
+    #[serde(default)]
+    avatar: Avatar,
+}
+
+/// The user object, as returned by Discord.
+#[derive(serde::Deserialize, Debug)]
+struct RawUser {
+    /// The display name, which is null if the user hasn't set one.
+    global_name: Option<String>,
+}
+
+/// The avatar of the user.
+#[derive(serde::Deserialize, Debug, Default)]
+struct Avatar {
+    /// The url of the avatar, which is null for accounts without a custom avatar.
+    link: Option<String>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum FetchError {
+    #[error("Request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Lookup responded with status {0}.")]
+    BadStatus(reqwest::StatusCode),
+    #[error("Unable to parse response: {0}")]
+    Deserialize(#[from] serde_json::Error),
+    #[error("Response is missing the {0} field.")]
+    MissingField(&'static str),
+}
+
+/// Fetches my Discord profile.
+async fn fetch() -> Result<Profile, FetchError> {
+    let response = reqwest::get(PROFILE_URL).await?;
+    if !response.status().is_success() {
+        return Err(FetchError::BadStatus(response.status()));
+    }
+
+    parse(&response.text().await?)
+}
+
+/// Parses a Discord lookup response into a profile.
+fn parse(json: &str) -> Result<Profile, FetchError> {
+    let user: DiscordUser = serde_json::from_str(json)?;
+    log::debug!("Profile avatar: {:?}", user.avatar.link);
+
+    let name = user
+        .raw
+        .global_name
+        .ok_or(FetchError::MissingField("global_name"))?;
+
+    Ok(Profile {
+        name,
+        avatar: user.avatar.link,
+    })
+}
+
+/// Fetches my Discord profile in the background, sending the progress through the sender.
+pub fn spawn_fetch(sender: mpsc::Sender<FetchState<Profile>>, ctx: egui::Context) {
+    fetch::spawn("profile", fetch(), sender, ctx);
+}
+
+/// The size the avatar is displayed at.
+const AVATAR_SIZE: f32 = 32.0;
+
+/// How long after fetching the profile before it can be refreshed, in seconds.
+pub const REFRESH_COOLDOWN: f64 = 10.0;
+
+/// Displays the given profile state, with a button to refresh it.
+///
+/// Returns true if the profile should be refreshed.
+pub fn show(ui: &mut egui::Ui, state: &FetchState<Profile>, can_refresh: bool) -> bool {
+    ui.horizontal(|ui| {
+        show_state(ui, state);
+
+        let loading = matches!(state, FetchState::Loading);
+        let refresh = ui
+            .add_enabled(can_refresh && !loading, egui::Button::new("⟳"))
+            .on_hover_text("Refresh profile")
+            .on_disabled_hover_text("The profile was refreshed recently.");
+        refresh.clicked()
+    })
+    .inner
+}
+
+/// Displays the given profile state.
+fn show_state(ui: &mut egui::Ui, state: &FetchState<Profile>) {
+    match state {
+        FetchState::Loading => {
+            // Placeholder the size of the avatar, so the layout doesn't jump once loaded.
+            ui.add_sized([AVATAR_SIZE, AVATAR_SIZE], egui::Spinner::new());
+            ui.label("Loading profile…");
+        }
+        FetchState::Failed(reason) => {
+            ui.label(format!("Unable to load profile: {reason}"));
+        }
+        FetchState::Done(profile) => {
+            if let Some(avatar) = &profile.avatar {
+                ui.add(
+                    egui::Image::from_uri(avatar)
+                        .fit_to_exact_size(egui::vec2(AVATAR_SIZE, AVATAR_SIZE))
+                        .rounding(AVATAR_SIZE / 2.0),
+                );
+            }
+            ui.label(format!("Discord: {}", profile.name));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_profile() {
+        let json =
+            r#"{"raw": {"global_name": "tye"}, "avatar": {"link": "https://example.com/a.png"}}"#;
+        let profile = parse(json).expect("The response should be valid.");
+
+        assert_eq!(profile.name, "tye");
+        assert_eq!(profile.avatar.as_deref(), Some("https://example.com/a.png"));
+    }
+
+    #[test]
+    fn malformed_json_is_a_deserialize_error() {
+        for json in ["", "{", "[]", r#"{"raw": "tye"}"#, r#"{"avatar": {}}"#] {
+            assert!(
+                matches!(parse(json), Err(FetchError::Deserialize(_))),
+                "{json} should fail to deserialize."
+            );
+        }
+    }
+
+    #[test]
+    fn missing_name_is_a_missing_field() {
+        let json = r#"{"raw": {"global_name": null}, "avatar": {"link": null}}"#;
+
+        assert!(matches!(
+            parse(json),
+            Err(FetchError::MissingField("global_name"))
+        ));
+    }
+}
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+/// How long without activity before repaints are throttled, in seconds.
+const IDLE_AFTER: f64 = 5.0;
+
+/// How often the app repaints while active, so logs & fetches show promptly.
+const ACTIVE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Throttles repaints once the app has had no activity for a while.
+///
+/// While active, the app repaints regularly to pick up new logs & fetches.
+/// Once throttled, the app only repaints on input.
+#[derive(Debug, Default)]
+pub struct Throttle {
+    /// When activity last occurred, in seconds since the app started.
+    last_activity: f64,
+    /// Whether repaints are currently throttled.
+    throttled: bool,
+}
+
+impl Throttle {
+    /// Updates the throttle from this frame's input & schedules the next repaint.
+    ///
+    /// `active` is whether anything other than input happened this frame,
+    /// such as a received log or fetch.
+    pub fn update(&mut self, ctx: &egui::Context, active: bool) {
+        let (now, input) = ctx.input(|i| (i.time, !i.events.is_empty()));
+
+        if input || active {
+            self.last_activity = now;
+        }
+
+        let throttled = now - self.last_activity > IDLE_AFTER;
+        if throttled != self.throttled {
+            log::debug!("Repaint throttled: {throttled}");
+            self.throttled = throttled;
+        }
+
+        if !self.throttled {
+            ctx.request_repaint_after(ACTIVE_INTERVAL);
+        }
+    }
+
+    /// Whether repaints are currently throttled.
+    pub fn throttled(&self) -> bool {
+        self.throttled
+    }
+}
@@ -0,0 +1,53 @@
+/// How strongly a page tint is blended into the panel background, from 0 to 1.
+const TINT_STRENGTH: f32 = 0.08;
+
+/// The minimum contrast ratio between text & a tinted background.
+/// This is the WCAG AA requirement for normal text.
+const MIN_CONTRAST: f32 = 4.5;
+
+/// Creates the central panel frame, with the background subtly tinted towards the given colour.
+///
+/// The tint is skipped if it would make the text hard to read.
+pub fn central_panel(style: &egui::Style, tint: Option<egui::Color32>) -> egui::Frame {
+    let frame = egui::Frame::central_panel(style);
+    let Some(tint) = tint else {
+        return frame;
+    };
+
+    let fill = blend(frame.fill, tint, TINT_STRENGTH);
+    match contrast_ratio(style.visuals.text_color(), fill) >= MIN_CONTRAST {
+        true => frame.fill(fill),
+        false => frame,
+    }
+}
+
+/// Linearly blends from one colour towards another by `t`, from 0 to 1.
+fn blend(from: egui::Color32, to: egui::Color32, t: f32) -> egui::Color32 {
+    let channel = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * t).round() as u8;
+
+    egui::Color32::from_rgb(
+        channel(from.r(), to.r()),
+        channel(from.g(), to.g()),
+        channel(from.b(), to.b()),
+    )
+}
+
+/// Calculates the WCAG contrast ratio between two colours, from 1 to 21.
+pub fn contrast_ratio(a: egui::Color32, b: egui::Color32) -> f32 {
+    let (a, b) = (luminance(a), luminance(b));
+    let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Calculates the relative luminance of a colour, from 0 to 1.
+fn luminance(colour: egui::Color32) -> f32 {
+    let linear = |channel: u8| {
+        let channel = channel as f32 / 255.0;
+        match channel <= 0.03928 {
+            true => channel / 12.92,
+            false => ((channel + 0.055) / 1.055).powf(2.4),
+        }
+    };
+
+    0.2126 * linear(colour.r()) + 0.7152 * linear(colour.g()) + 0.0722 * linear(colour.b())
+}
@@ -0,0 +1,149 @@
+use crate::logger;
+
+/// How long non-error toasts are shown for, in seconds.
+const TOAST_DURATION: f64 = 3.0;
+
+/// How long toasts take to fade out at the end of their duration, in seconds.
+const FADE_DURATION: f64 = 0.5;
+
+/// The most toasts shown at once, the oldest are removed past this.
+pub const MAX_TOASTS: usize = 5;
+
+/// A notification shown on top of the app.
+#[derive(Debug)]
+pub struct Toast {
+    /// The severity of the notification.
+    pub level: log::Level,
+    /// The text of the notification.
+    pub message: String,
+    /// The id of the log entry this toast was created from, if any.
+    pub log_id: Option<usize>,
+    /// How many identical toasts this toast stands for.
+    pub count: usize,
+    /// When the toast was first shown, in seconds since the app started.
+    shown: Option<f64>,
+}
+
+impl Toast {
+    /// Creates a new [`Toast`], which expires relative to when it is first shown.
+    pub fn new(level: log::Level, message: String, log_id: Option<usize>) -> Self {
+        Self {
+            level,
+            message,
+            log_id,
+            count: 1,
+            shown: None,
+        }
+    }
+
+    /// Whether this toast stays until it is dismissed.
+    ///
+    /// Errors persist so they aren't missed, other levels fade away.
+    pub fn persistent(&self) -> bool {
+        self.level == log::Level::Error
+    }
+
+    /// How visible this toast is, fading from 1 to 0 as it expires.
+    fn opacity(&self, now: f64) -> f32 {
+        if self.persistent() {
+            return 1.0;
+        }
+
+        let remaining = self.shown.unwrap_or(now) + TOAST_DURATION - now;
+        (remaining / FADE_DURATION).clamp(0.0, 1.0) as f32
+    }
+}
+
+/// Adds the toast to be displayed.
+///
+/// An identical toast is replaced by this one & counts it, so repeats don't flood the screen.
+/// Past [`MAX_TOASTS`], the oldest toasts are removed.
+pub fn push(toasts: &mut Vec<Toast>, mut toast: Toast) {
+    let identical = toasts
+        .iter()
+        .position(|shown| shown.level == toast.level && shown.message == toast.message);
+    if let Some(index) = identical {
+        toast.count += toasts.remove(index).count;
+    }
+
+    toasts.push(toast);
+    if toasts.len() > MAX_TOASTS {
+        toasts.drain(..toasts.len() - MAX_TOASTS);
+    }
+}
+
+/// A click on the body of a toast.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Clicked {
+    /// The id of the log entry the clicked toast was created from, if any.
+    pub log_id: Option<usize>,
+}
+
+/// Displays the toasts stacked in the bottom right corner.
+/// Expired & dismissed toasts are removed.
+///
+/// Returns the click on the body of a toast, if there was one.
+pub fn show(ctx: &egui::Context, toasts: &mut Vec<Toast>) -> Option<Clicked> {
+    let now = ctx.input(|i| i.time);
+    for toast in toasts.iter_mut() {
+        toast.shown.get_or_insert(now);
+    }
+    toasts.retain(|toast| toast.opacity(now) > 0.0);
+
+    if toasts.is_empty() {
+        return None;
+    }
+
+    let mut clicked = None;
+    let mut dismissed = None;
+
+    egui::Area::new(egui::Id::new("toasts"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, [-8.0, -8.0])
+        .show(ctx, |ui| {
+            for (index, toast) in toasts.iter().enumerate() {
+                ui.scope(|ui| {
+                    ui.set_opacity(toast.opacity(now));
+
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            let colour = logger::level_colour(ui.visuals(), toast.level);
+
+                            let text = match toast.count {
+                                1 => toast.message.clone(),
+                                count => format!("{} (×{count})", toast.message),
+                            };
+                            let body = ui.add(
+                                egui::Label::new(egui::RichText::new(text).color(colour))
+                                    .sense(egui::Sense::click()),
+                            );
+                            if body.clicked() {
+                                clicked = Some(Clicked {
+                                    log_id: toast.log_id,
+                                });
+                            }
+
+                            if toast.persistent() {
+                                if ui.small_button("Copy").clicked() {
+                                    ui.ctx().copy_text(toast.message.clone());
+                                }
+                                if ui.small_button("✖").clicked() {
+                                    dismissed = Some(index);
+                                }
+                            }
+                        });
+                    });
+                });
+            }
+        });
+
+    if let Some(index) = dismissed {
+        toasts.remove(index);
+    }
+
+    // Keeps repainting so fading toasts animate & expire.
+    if toasts.iter().any(|toast| !toast.persistent()) {
+        ctx.request_repaint();
+    }
+
+    clicked
+}